@@ -25,7 +25,47 @@ pub struct SampleRange {
 #[derive(Debug, Clone, PartialEq)]
 pub struct DecimalValue {
   pub integer: Value,
-  pub decimal: Option<Value>,
+  /// The fraction digits as written in the sample, so that trailing zeros (`1.50`) are kept for operand computation.
+  pub decimal: Option<String>,
+}
+
+/// The maximum number of values produced when enumerating a sample range.
+pub const MAX_SAMPLE_EXPANSION: usize = 1000;
+
+/// The integer values enumerated from a sample range.
+///
+/// When the range holds more values than the expansion limit, only the first values are kept and `truncated` is set.
+/// The bounds of the original range are always reported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleExpansion {
+  pub lower: usize,
+  pub upper: usize,
+  pub values: Vec<usize>,
+  pub truncated: bool,
+}
+
+impl SampleRange {
+  /// Enumerates the integer values of the range, capped to [`MAX_SAMPLE_EXPANSION`] values.
+  pub fn expand(&self) -> SampleExpansion {
+    self.expand_with_limit(MAX_SAMPLE_EXPANSION)
+  }
+
+  /// Enumerates the integer values of the range, capped to `limit` values.
+  pub fn expand_with_limit(&self, limit: usize) -> SampleExpansion {
+    let lower = self.lower_val.integer.0;
+    let upper = self.upper_val.as_ref().map_or(lower, |upper| upper.integer.0).max(lower);
+    let count = upper - lower + 1;
+    let values = (lower..=upper).take(limit).collect();
+
+    SampleExpansion { lower, upper, values, truncated: count > limit }
+  }
+}
+
+impl SampleList {
+  /// Enumerates the integer values of every range of the list, each capped to [`MAX_SAMPLE_EXPANSION`] values.
+  pub fn expand(&self) -> Vec<SampleExpansion> {
+    self.sample_ranges.iter().map(SampleRange::expand).collect()
+  }
 }
 
 /// A complete (and the only complete) AST representation of a plural rule. Comprises a vector of AndConditions.
@@ -73,7 +113,7 @@ pub struct DecimalValue {
 ///   parse_plural_rule("i is 5 or v within 2").expect("Parsing succeeded").condition
 /// )
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Condition(pub Vec<AndCondition>);
 
 /// An incomplete AST representation of a plural rule. Comprises a vector of Relations.
@@ -104,9 +144,65 @@ pub struct Condition(pub Vec<AndCondition>);
 ///   },
 /// ]);
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct AndCondition(pub Vec<Relation>);
 
+impl Condition {
+  /// Returns the canonical form of the condition, so that two equivalent conditions compare equal.
+  ///
+  /// The relations of each AndCondition and the AndConditions are sorted and deduplicated, the synonymous operators
+  /// and operands are replaced by a single one and the overlapping ranges of each RangeList are merged.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use cldr_pluralrules_parser::parse_plural_rule;
+  ///
+  /// let first = parse_plural_rule("i = 5 or i = 7").expect("Parsing succeeded").condition;
+  /// let second = parse_plural_rule("i is 7 or i in 5").expect("Parsing succeeded").condition;
+  ///
+  /// assert_eq!(first.normalize(), second.normalize());
+  /// ```
+  pub fn normalize(&self) -> Condition {
+    let mut and_conditions: Vec<AndCondition> = self.0.iter().map(AndCondition::normalize).collect();
+    and_conditions.sort();
+    and_conditions.dedup();
+    Condition(and_conditions)
+  }
+}
+
+impl AndCondition {
+  /// Returns the canonical form of the AndCondition, its relations being normalized, sorted and deduplicated.
+  pub fn normalize(&self) -> AndCondition {
+    let mut relations: Vec<Relation> = self.0.iter().map(Relation::normalize).collect();
+    relations.sort();
+    relations.dedup();
+    AndCondition(relations)
+  }
+}
+
+impl Relation {
+  /// Returns the canonical form of the relation, using `in` for `is` and `=`, `c` for `e` and a merged RangeList.
+  pub fn normalize(&self) -> Relation {
+    let operator = match self.operator {
+      Operator::In | Operator::Is | Operator::EQ => Operator::In,
+      Operator::NotIn | Operator::IsNot | Operator::NotEQ => Operator::NotIn,
+      Operator::Within => Operator::Within,
+      Operator::NotWithin => Operator::NotWithin,
+    };
+    let operand = match self.expression.operand {
+      Operand::E => Operand::C,
+      ref operand => operand.clone(),
+    };
+
+    Relation {
+      expression: Expression { operand, modulus: self.expression.modulus.clone() },
+      operator,
+      range_list: self.range_list.normalize(),
+    }
+  }
+}
+
 /// An incomplete AST representation of a plural rule. Comprises an Expression, an Operator, and a RangeList.
 ///
 /// # Examples
@@ -128,7 +224,7 @@ pub struct AndCondition(pub Vec<Relation>);
 ///   range_list: RangeList(vec![RangeListItem::Value(Value(3))]),
 /// };
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Relation {
   pub expression: Expression,
   pub operator: Operator,
@@ -149,7 +245,7 @@ pub struct Relation {
 /// | IsNot | "is not" |
 /// | EQ | "=" |
 /// | NotEq | "!=" |
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Operator {
   In,
   NotIn,
@@ -178,7 +274,7 @@ pub enum Operator {
 ///
 /// Expression { operand: Operand::I, modulus: Some(Modulo(Value(100))) };
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Expression {
   pub operand: Operand,
   pub modulus: Option<Modulo>,
@@ -201,7 +297,7 @@ pub struct Expression {
 ///
 /// Modulo(Value(100));
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Modulo(pub Value);
 
 /// An incomplete AST representation of a plural rule. Comprises a char.
@@ -221,7 +317,7 @@ pub struct Modulo(pub Value);
 ///
 /// Operand::I;
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Operand {
   C, // compact decimal exponent value: exponent of the power of 10 used in compact decimal formatting
   E, // deprecated synonym of C
@@ -268,13 +364,67 @@ impl From<Operand> for &str {
 ///   RangeListItem::Value(Value(9)),
 /// ]);
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct RangeList(pub Vec<RangeListItem>);
 
+impl RangeList {
+  /// Returns the canonical form of the RangeList, its items being sorted and the overlapping ones merged.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use cldr_pluralrules_parser::ast::*;
+  ///
+  /// let range_list = RangeList(vec![
+  ///   RangeListItem::Range(Range { lower_val: Value(2), upper_val: Value(4) }),
+  ///   RangeListItem::Range(Range { lower_val: Value(1), upper_val: Value(3) }),
+  /// ]);
+  ///
+  /// assert_eq!(
+  ///   RangeList(vec![RangeListItem::Range(Range { lower_val: Value(1), upper_val: Value(4) })]),
+  ///   range_list.normalize()
+  /// );
+  /// ```
+  pub fn normalize(&self) -> RangeList {
+    let mut bounds: Vec<(usize, usize)> = self
+      .0
+      .iter()
+      .map(|item| {
+        match item {
+          RangeListItem::Range(range) => (range.lower_val.0, range.upper_val.0),
+          RangeListItem::Value(value) => (value.0, value.0),
+        }
+      })
+      .collect();
+    bounds.sort();
+
+    let mut merged: Vec<(usize, usize)> = Vec::with_capacity(bounds.len());
+    for (lower, upper) in bounds {
+      match merged.last_mut() {
+        Some((_, last_upper)) if lower <= *last_upper => *last_upper = upper.max(*last_upper),
+        _ => merged.push((lower, upper)),
+      }
+    }
+
+    RangeList(
+      merged
+        .into_iter()
+        .map(|(lower, upper)| {
+          if lower == upper {
+            RangeListItem::Value(Value(lower))
+          } else {
+            RangeListItem::Range(Range { lower_val: Value(lower), upper_val: Value(upper) })
+          }
+        })
+        .collect(),
+    )
+  }
+}
+
 /// An enum of items that appear in a RangeList: Range or a Value.
 ///
 /// See Range and Value for additional details.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum RangeListItem {
   Range(Range),
   Value(Value),
@@ -297,7 +447,7 @@ pub enum RangeListItem {
 ///
 /// RangeListItem::Range(Range { lower_val: Value(11), upper_val: Value(15) });
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Range {
   pub lower_val: Value,
   pub upper_val: Value,
@@ -320,5 +470,5 @@ pub struct Range {
 ///
 /// RangeListItem::Value(Value(99));
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Value(pub usize);
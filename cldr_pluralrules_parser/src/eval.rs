@@ -0,0 +1,135 @@
+//! Evaluation of the plural rules AST against the plural operands of a number.
+//!
+//! A condition can be evaluated directly with `matches`, walking the AST on every call, or compiled once with
+//! `compile` into a closure evaluating it without walking the AST again.
+use intl_pluralrules::operands::PluralOperands;
+
+use crate::ast::*;
+
+/// A compiled condition, checking if the plural operands satisfy it.
+type CompiledCondition = Box<dyn Fn(&PluralOperands) -> bool>;
+
+/// Reads the value of an operand from the plural operands.
+fn operand_value(operand: &Operand, operands: &PluralOperands) -> f64 {
+  match operand {
+    Operand::C => operands.c as f64,
+    Operand::E => operands.e as f64,
+    Operand::N => operands.n,
+    Operand::I => operands.i as f64,
+    Operand::V => operands.v as f64,
+    Operand::W => operands.w as f64,
+    Operand::F => operands.f as f64,
+    Operand::T => operands.t as f64,
+  }
+}
+
+/// The bounds of a RangeList item, along with whether only the integers between them match.
+fn range_bounds(item: &RangeListItem, integers_only: bool) -> (f64, f64, bool) {
+  match item {
+    RangeListItem::Value(value) => (value.0 as f64, value.0 as f64, false),
+    RangeListItem::Range(range) => (range.lower_val.0 as f64, range.upper_val.0 as f64, integers_only),
+  }
+}
+
+/// Checks if the value is within one of the bounds.
+fn within_bounds(value: f64, bounds: &[(f64, f64, bool)]) -> bool {
+  bounds
+    .iter()
+    .any(|(lower, upper, integers_only)| (!integers_only || value.fract() == 0.0) && *lower <= value && value <= *upper)
+}
+
+/// Checks if the operator is a negated one, like `not in` or `!=`.
+fn is_negated(operator: &Operator) -> bool {
+  matches!(operator, Operator::NotIn | Operator::NotWithin | Operator::IsNot | Operator::NotEQ)
+}
+
+impl Expression {
+  /// Evaluates the expression, the operand modulo the modulus if any, against the plural operands.
+  pub fn value(&self, operands: &PluralOperands) -> f64 {
+    let value = operand_value(&self.operand, operands);
+    match &self.modulus {
+      Some(Modulo(modulus)) => value % modulus.0 as f64,
+      None => value,
+    }
+  }
+}
+
+impl Relation {
+  /// The bounds of the RangeList of the relation, a range of `in` only matching integers, unlike one of `within`.
+  fn bounds(&self) -> Vec<(f64, f64, bool)> {
+    let integers_only = !matches!(self.operator, Operator::Within | Operator::NotWithin);
+    self.range_list.0.iter().map(|item| range_bounds(item, integers_only)).collect()
+  }
+
+  /// Checks if the plural operands satisfy the relation.
+  pub fn matches(&self, operands: &PluralOperands) -> bool {
+    within_bounds(self.expression.value(operands), &self.bounds()) != is_negated(&self.operator)
+  }
+
+  /// Compiles the relation into a closure checking if the plural operands satisfy it.
+  pub fn compile(&self) -> impl Fn(&PluralOperands) -> bool {
+    let expression = self.expression.clone();
+    let bounds = self.bounds();
+    let negated = is_negated(&self.operator);
+    move |operands| within_bounds(expression.value(operands), &bounds) != negated
+  }
+}
+
+impl AndCondition {
+  /// Checks if the plural operands satisfy every relation of the AndCondition.
+  pub fn matches(&self, operands: &PluralOperands) -> bool {
+    self.0.iter().all(|relation| relation.matches(operands))
+  }
+
+  /// Compiles the AndCondition into a closure checking if the plural operands satisfy every relation.
+  pub fn compile(&self) -> impl Fn(&PluralOperands) -> bool {
+    let relations: Vec<CompiledCondition> =
+      self.0.iter().map(|relation| Box::new(relation.compile()) as CompiledCondition).collect();
+    move |operands| relations.iter().all(|relation| relation(operands))
+  }
+}
+
+impl Condition {
+  /// Checks if the plural operands satisfy one of the AndConditions of the condition.
+  ///
+  /// An empty condition, like the one of the `other` category, matches every number.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use std::convert::TryFrom;
+  ///
+  /// use cldr_pluralrules_parser::parse_plural_rule;
+  /// use intl_pluralrules::operands::PluralOperands;
+  ///
+  /// let condition = parse_plural_rule("i = 1 and v = 0").expect("Parsing succeeded").condition;
+  ///
+  /// assert!(condition.matches(&PluralOperands::from(1_u64)));
+  /// assert!(!condition.matches(&PluralOperands::try_from("1.5").expect("Parsing succeeded")));
+  /// ```
+  pub fn matches(&self, operands: &PluralOperands) -> bool {
+    self.0.is_empty() || self.0.iter().any(|and_condition| and_condition.matches(operands))
+  }
+
+  /// Compiles the condition into a closure checking if the plural operands satisfy it, so that evaluating it many
+  /// times, like in a hot loop, does not walk the AST again.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use cldr_pluralrules_parser::parse_plural_rule;
+  /// use intl_pluralrules::operands::PluralOperands;
+  ///
+  /// let condition =
+  ///   parse_plural_rule("n % 10 = 2..4 and n % 100 != 12..14").expect("Parsing succeeded").condition;
+  /// let is_few = condition.compile();
+  ///
+  /// assert!(is_few(&PluralOperands::from(22_u64)));
+  /// assert!(!is_few(&PluralOperands::from(12_u64)));
+  /// ```
+  pub fn compile(&self) -> impl Fn(&PluralOperands) -> bool {
+    let and_conditions: Vec<CompiledCondition> =
+      self.0.iter().map(|and_condition| Box::new(and_condition.compile()) as CompiledCondition).collect();
+    move |operands| and_conditions.is_empty() || and_conditions.iter().any(|and_condition| and_condition(operands))
+  }
+}
@@ -30,6 +30,8 @@
 
 /// A public AST module for plural rule representations.
 pub mod ast;
+/// A public module evaluating plural rules against the plural operands of a number.
+pub mod eval;
 /// A private parsing module for plural rules.
 mod parser;
 
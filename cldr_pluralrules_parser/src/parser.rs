@@ -103,7 +103,9 @@ fn and_condition(i: &str) -> IResult<&str, AndCondition> {
 }
 
 fn decimal_value(i: &str) -> IResult<&str, DecimalValue> {
-  map(tuple((value, opt(preceded(tag("."), value)))), |(integer, decimal)| DecimalValue { integer, decimal })(i)
+  map(tuple((value, opt(preceded(tag("."), alphanumeric1)))), |(integer, decimal)| {
+    DecimalValue { integer, decimal: decimal.map(String::from) }
+  })(i)
 }
 
 fn sample_range(i: &str) -> IResult<&str, SampleRange> {
@@ -249,3 +249,85 @@ fn be_other_rule() {
     result.unwrap().condition
   );
 }
+
+#[test]
+fn decimal_samples_keep_trailing_zeros() {
+  let test = "v = 2 @decimal 1.50, 2.00";
+  let samples = parse_plural_rule(test).expect("Parsing succeeded").samples.expect("Samples are present");
+
+  assert_eq!(
+    Some(SampleList {
+      sample_ranges: vec![
+        SampleRange { lower_val: DecimalValue { integer: Value(1), decimal: Some("50".to_string()) }, upper_val: None },
+        SampleRange { lower_val: DecimalValue { integer: Value(2), decimal: Some("00".to_string()) }, upper_val: None },
+      ],
+      ellipsis: false,
+    }),
+    samples.decimal
+  );
+}
+
+#[test]
+fn integer_samples_are_expanded() {
+  let test = "n = 2..4 @integer 2~4, 22";
+  let samples = parse_plural_rule(test).expect("Parsing succeeded").samples.expect("Samples are present");
+  let expanded = samples.integer.expect("Integer samples are present").expand();
+
+  assert_eq!(
+    vec![SampleExpansion { lower: 2, upper: 4, values: vec![2, 3, 4], truncated: false }, SampleExpansion {
+      lower: 22,
+      upper: 22,
+      values: vec![22],
+      truncated: false
+    },],
+    expanded
+  );
+}
+
+#[test]
+fn large_sample_ranges_are_truncated() {
+  let test = "n = 0 @integer 0~1000000000";
+  let samples = parse_plural_rule(test).expect("Parsing succeeded").samples.expect("Samples are present");
+  let range = &samples.integer.expect("Integer samples are present").sample_ranges[0];
+
+  let expanded = range.expand();
+  assert_eq!(0, expanded.lower);
+  assert_eq!(1000000000, expanded.upper);
+  assert_eq!(MAX_SAMPLE_EXPANSION, expanded.values.len());
+  assert!(expanded.truncated);
+
+  let expanded = range.expand_with_limit(3);
+  assert_eq!(vec![0, 1, 2], expanded.values);
+  assert!(expanded.truncated);
+}
+
+#[test]
+fn normalize_sorts_and_conditions() {
+  let first = parse_plural_rule("i = 5 or i = 7").expect("Parsing succeeded").condition;
+  let second = parse_plural_rule("i = 7 or i = 5").expect("Parsing succeeded").condition;
+
+  assert_ne!(first, second);
+  assert_eq!(first.normalize(), second.normalize());
+
+  let first = parse_plural_rule("i = 5, 7 and v = 0").expect("Parsing succeeded").condition;
+  let second = parse_plural_rule("v is 0 and i in 7, 5").expect("Parsing succeeded").condition;
+
+  assert_eq!(first.normalize(), second.normalize());
+}
+
+#[test]
+fn normalize_merges_overlapping_ranges() {
+  let condition = parse_plural_rule("n = 1..3, 2..4, 4, 6").expect("Parsing succeeded").condition;
+
+  assert_eq!(
+    Condition(vec![AndCondition(vec![Relation {
+      expression: Expression { operand: Operand::N, modulus: None },
+      operator: Operator::In,
+      range_list: RangeList(vec![
+        RangeListItem::Range(Range { lower_val: Value(1), upper_val: Value(4) }),
+        RangeListItem::Value(Value(6)),
+      ]),
+    }])]),
+    condition.normalize()
+  );
+}
@@ -0,0 +1,57 @@
+use std::convert::TryFrom;
+
+use cldr_pluralrules_parser::parse_plural_rule;
+use intl_pluralrules::operands::PluralOperands;
+use pretty_assertions::assert_eq;
+
+const RULES: &[&str] = &[
+  "",
+  "i = 1 and v = 0",
+  "i = 0,1",
+  "n = 0..1 or n = 11..99",
+  "n within 0..2 and n != 2",
+  "v = 0 and i % 10 = 1 and i % 100 != 11",
+  "v = 0 and i % 10 = 2..4 and i % 100 != 12..14",
+  "v = 0 and i % 10 = 0 or v = 0 and i % 10 = 5..9 or v = 0 and i % 100 = 11..14",
+  "n % 10 = 3..4,9 and n % 100 != 10..19,70..79,90..99",
+  "e = 0 and i != 0 and i % 1000000 = 0 and v = 0 or e != 0..5",
+  "f % 10 = 1 and f % 100 != 11 or t = 1",
+];
+
+fn operands() -> Vec<PluralOperands> {
+  let integers = (0_u64..=250).map(PluralOperands::from);
+  let decimals = ["0.0", "0.5", "1.0", "1.5", "2.0", "2.5", "11.1", "12.25", "13.0", "101.10", "1000000"]
+    .iter()
+    .map(|number| PluralOperands::try_from(*number).expect("Parsing of the number succeeded"));
+  integers.chain(decimals).collect()
+}
+
+#[test]
+fn compiled_condition_matches_the_evaluated_one() {
+  let operands = operands();
+  for rule in RULES {
+    let condition = parse_plural_rule(rule).expect("Parsing succeeded").condition;
+    let compiled = condition.compile();
+    for operands in &operands {
+      assert_eq!(condition.matches(operands), compiled(operands), "{rule} for {operands:?}");
+    }
+  }
+}
+
+#[test]
+fn condition_matches_the_numbers_of_its_category() {
+  let matching = |rule: &str| {
+    let condition = parse_plural_rule(rule).expect("Parsing succeeded").condition;
+    (0_u64..=30).filter(|number| condition.matches(&PluralOperands::from(*number))).collect::<Vec<_>>()
+  };
+
+  assert_eq!(matching("i = 1 and v = 0"), vec![1]);
+  assert_eq!(matching("v = 0 and i % 10 = 2..4 and i % 100 != 12..14"), vec![2, 3, 4, 22, 23, 24]);
+  assert_eq!(matching("n within 0..2 and n != 2"), vec![0, 1]);
+  assert_eq!(matching(""), (0..=30).collect::<Vec<_>>());
+
+  let condition = parse_plural_rule("n = 0..1").expect("Parsing succeeded").condition;
+  assert!(!condition.matches(&PluralOperands::try_from("0.5").expect("Parsing succeeded")));
+  let condition = parse_plural_rule("n within 0..1").expect("Parsing succeeded").condition;
+  assert!(condition.matches(&PluralOperands::try_from("0.5").expect("Parsing succeeded")));
+}
@@ -1,20 +1,40 @@
 //! This module provides the CLI for the i18n system.
-use std::path::PathBuf;
+use std::{
+  io::BufRead,
+  path::{Path, PathBuf},
+};
 
 use anstyle::Style;
-use clap::{builder::Styles, command, Parser};
+use clap::{builder::Styles, Parser, ValueEnum};
 use clap_complete::Shell;
-use color_eyre::{eyre::eyre, Section, SectionExt};
+use color_eyre::{eyre::eyre, owo_colors::OwoColorize, Section, SectionExt};
 use i18next_parser_core::{
+  check_consistency,
+  check_default_values,
+  check_namespace_sizes,
+  consolidate_catalogs,
+  explain_globs,
+  export_pot,
+  find_inconsistencies,
+  find_input_files,
+  flatten_entries,
   generate_types,
   log_time,
   merge_all_values,
-  parse_directory,
+  parse_files,
   print_config,
+  print_glob_explanations,
+  print_profile,
+  print_summary,
   write_to_file,
+  CatalogChanges,
   Config,
+  ConfigFileFormat,
+  Inconsistency,
+  MergeResults,
+  Profile,
 };
-use log::{info, trace};
+use log::{debug, info};
 use resolve_path::PathResolveExt;
 
 /// Create the style used by the CLI
@@ -24,6 +44,24 @@ fn make_style() -> Styles {
     .literal(Style::new().bold().fg_color(Some(anstyle::Color::Ansi(anstyle::AnsiColor::Yellow))))
 }
 
+/// The formats of the configuration file created by `--init`
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq)]
+enum InitFormat {
+  Json,
+  Json5,
+  Yaml,
+}
+
+impl From<InitFormat> for ConfigFileFormat {
+  fn from(value: InitFormat) -> Self {
+    match value {
+      InitFormat::Json => ConfigFileFormat::Json,
+      InitFormat::Json5 => ConfigFileFormat::Json5,
+      InitFormat::Yaml => ConfigFileFormat::Yaml,
+    }
+  }
+}
+
 /// The CLI options
 #[derive(Parser, Debug)]
 #[command(version, about, author, long_about= None, styles=make_style())]
@@ -40,6 +78,65 @@ pub struct Cli {
   #[cfg(feature = "generate_types")]
   generate_types: bool,
 
+  /// The directory where old catalogs are gathered, relative to the path
+  #[arg(long, value_name = "DIR", global = true, value_hint = clap::ValueHint::DirPath)]
+  backup_dir: Option<String>,
+
+  /// Should archive the removed keys to the old catalogs, even when `createOldCatalogs` is not set
+  #[arg(long, default_value = "false", global = true)]
+  keep_backup_on_clean: bool,
+
+  /// Update only the keys under this key path of the catalogs, like `common.dialog`
+  #[arg(long, value_name = "KEY", global = true)]
+  scope: Option<String>,
+
+  /// Should report the keys extracted without a default value
+  #[arg(long, default_value = "false", global = true)]
+  require_default: bool,
+
+  /// Should report the keys that differ between the primary locale and the other ones
+  #[arg(long, default_value = "false", global = true)]
+  check_consistency: bool,
+
+  /// Check the catalogs without writing them, failing when some would be updated or are inconsistent
+  #[arg(long, default_value = "false", global = true)]
+  check: bool,
+
+  /// Should exit with 2 when keys were only added, 3 when keys were removed, and 4 when values were only reset
+  #[arg(long, default_value = "false", global = true)]
+  only_changed_exit_code: bool,
+
+  /// Export the default locale keys as a flat `namespace:key` JSON file, relative to the path
+  #[arg(long, value_name = "FILE", global = true, value_hint = clap::ValueHint::FilePath)]
+  export_flat: Option<PathBuf>,
+
+  /// Export the default locale keys as a gettext template (POT) file, relative to the path
+  #[arg(long, value_name = "FILE", global = true, value_hint = clap::ValueHint::FilePath)]
+  export_pot: Option<PathBuf>,
+
+  /// Write every locale and namespace into a single `{ locale: { namespace: keys } }` JSON file, relative to the path,
+  /// instead of the catalogs
+  #[arg(long, value_name = "FILE", global = true, value_hint = clap::ValueHint::FilePath)]
+  merge_into: Option<PathBuf>,
+
+  /// Print the files matched and ignored by each input glob, then exit
+  #[arg(long, default_value = "false", global = true)]
+  explain_globs: bool,
+
+  /// Parse only the newline-separated files read from the standard input, relative to the path, instead of the input
+  /// globs, keeping the keys of the other files
+  #[arg(long, default_value = "false", global = true)]
+  stdin: bool,
+
+  /// Print the time spent globbing, parsing, merging and writing
+  #[arg(long, default_value = "false", global = true)]
+  profile: bool,
+
+  /// Create a default configuration file in the path, in the given format
+  #[arg(long, value_name = "FORMAT", num_args = 0..=1, default_missing_value = "json")]
+  #[clap(value_enum)]
+  init: Option<InitFormat>,
+
   /// Should generate shell completions
   #[arg(long)]
   #[clap(value_enum)]
@@ -53,33 +150,166 @@ impl Cli {
   }
 }
 
+/// Read the newline-separated file paths, relative to the path, skipping the blank lines and the missing files
+fn read_file_list<R: BufRead>(reader: R, path: &Path) -> color_eyre::Result<Vec<PathBuf>> {
+  let mut files = vec![];
+  for line in reader.lines() {
+    let line = line?;
+    let line = line.trim();
+    if line.is_empty() {
+      continue;
+    }
+    let file = path.join(line);
+    if file.is_file() {
+      files.push(file);
+    } else {
+      debug!("Skipping missing file {}", file.display().yellow());
+    }
+  }
+  Ok(files)
+}
+
+/// Get the exit code matching the changes applied to the catalogs
+fn get_exit_code(changes: CatalogChanges) -> u8 {
+  match changes {
+    CatalogChanges::Unchanged => 0,
+    CatalogChanges::Additions => 2,
+    CatalogChanges::Removals => 3,
+    CatalogChanges::Resets => 4,
+  }
+}
+
+/// List the issues found by the `--check` mode, the catalogs that would be updated and the inconsistent ones
+fn check_report(merged: &[MergeResults], inconsistencies: &[Inconsistency], config: &Config) -> Vec<String> {
+  let separator = &config.namespace_separator;
+  let updates = merged.iter().filter(|result| result.is_changed()).map(|result| {
+    format!("{}{separator}{} would be updated ({})", result.locale, result.namespace, result.path.display())
+  });
+  let inconsistencies = inconsistencies.iter().flat_map(|inconsistency| {
+    let catalog = format!("{}{separator}{}", inconsistency.locale, inconsistency.namespace);
+    let extra = inconsistency.extra.iter().map(|key| format!("has the extra key {key}"));
+    let missing = inconsistency.missing.iter().map(|key| format!("is missing the key {key}"));
+    extra.chain(missing).map(move |issue| format!("{catalog} {issue}"))
+  });
+  updates.chain(inconsistencies).collect()
+}
+
 pub trait Runnable {
-  fn run(&self) -> color_eyre::Result<()>;
+  /// Run the command, returning the exit code of the process
+  fn run(&self) -> color_eyre::Result<u8>;
 }
 
 impl Runnable for Cli {
-  fn run(&self) -> color_eyre::Result<()> {
+  fn run(&self) -> color_eyre::Result<u8> {
+    let mut profile = Profile::default();
+    let result = self.run_with_profile(&mut profile, std::io::stdin().lock());
+    if self.profile {
+      print_profile(&profile);
+    }
+    result
+  }
+}
+
+impl Cli {
+  /// Run the command, recording the duration of its phases in the profile and reading the file list of `--stdin`
+  /// from the given reader
+  fn run_with_profile<R: BufRead>(&self, profile: &mut Profile, stdin: R) -> color_eyre::Result<u8> {
     let path = &self.path;
+    if let Some(format) = self.init {
+      Config::init(path, format.into())?;
+      return Ok(0);
+    }
     log_time!(format!("Parsing {} to find translations to extract", path.display().yellow()), {
       info!("Working directory: {}", path.display().yellow());
-      let config = &Config::new(path, self.verbose)?;
+      let mut config = Config::new(path, self.verbose)?;
+      if let Some(backup_dir) = &self.backup_dir {
+        config.backup_dir = Some(backup_dir.clone());
+      }
+      if self.keep_backup_on_clean {
+        config.keep_backup_on_clean = true;
+      }
+      if let Some(scope) = &self.scope {
+        config.scope = Some(scope.clone());
+      }
+      if self.require_default {
+        config.require_default = true;
+      }
+      if self.check_consistency {
+        config.check_consistency = true;
+      }
+      if self.stdin {
+        // the keys of the files that are not listed are not extracted, so none can be considered as removed
+        config.keep_removed = true;
+      }
+      let config = &config;
       trace!("Configuration: {config:?}");
 
       print_config(config);
 
       let path = &path.resolve();
+      if self.explain_globs {
+        print_glob_explanations(&explain_globs(path.clone(), config)?);
+        return Ok(0);
+      }
       let file_name = path.file_name().ok_or(eyre!("Invalid path").note(format!("{path:#?}").header("Path: ")))?;
       let merged = log_time!(format!("Parsing directory {:?}", file_name.yellow()), {
-        let entries = parse_directory(path.clone(), config)?;
-        let merged = merge_all_values(entries, config)?;
-        write_to_file(&merged, config)?;
+        let files = if self.stdin {
+          profile.record("Reading file list", || read_file_list(stdin, path))?
+        } else {
+          profile.record("Glob expansion", || find_input_files(path.clone(), config))?
+        };
+        let entries = profile.record("Parsing", || parse_files(&files, config));
+        check_default_values(&entries, config)?;
+        let merged = profile.record("Merging", || merge_all_values(entries, config))?;
+        if self.check {
+          let report = check_report(&merged, &find_inconsistencies(&merged, config), config);
+          if report.is_empty() {
+            info!("The catalogs are up to date");
+            return Ok(0);
+          }
+          return Err(eyre!("The check found {} issues", report.len()).section(report.join("\n").header("Issues:")));
+        }
+        check_consistency(&merged, config)?;
+        check_namespace_sizes(&merged, config)?;
+        if config.fail_on_update && merged.iter().any(MergeResults::is_changed) {
+          return Err(eyre!("Some catalogs would be updated, but fail_on_update is set"));
+        }
+        profile.record("Writing", || -> color_eyre::Result<()> {
+          match &self.merge_into {
+            Some(merge_into) => {
+              let merge_path = path.join(merge_into);
+              log_time!(format!("Writing consolidated catalogs to {}", merge_path.display().yellow()), {
+                std::fs::write(&merge_path, serde_json::to_string_pretty(&consolidate_catalogs(&merged))?)
+              })?;
+            },
+            None => write_to_file(&merged, config)?,
+          }
+          Ok(())
+        })?;
+        print_summary(&merged);
 
         merged
       });
+      if let Some(export_flat) = &self.export_flat {
+        let export_path = path.join(export_flat);
+        log_time!(format!("Exporting flat keys to {}", export_path.display().yellow()), {
+          let flat = flatten_entries(&merged);
+          std::fs::write(&export_path, serde_json::to_string_pretty(&flat)?)
+        })?;
+      }
+      if let Some(pot_file) = &self.export_pot {
+        let export_path = path.join(pot_file);
+        log_time!(format!("Exporting gettext template to {}", export_path.display().yellow()), {
+          std::fs::write(&export_path, export_pot(&merged, config))
+        })?;
+      }
       if cfg!(feature = "generate_types") && self.generate_types {
-        log_time!("Generating types", { generate_types(&merged, config) })
+        log_time!("Generating types", { generate_types(&merged, config) })?;
+      }
+      if self.only_changed_exit_code {
+        Ok(get_exit_code(CatalogChanges::from_results(&merged)))
       } else {
-        Ok(())
+        Ok(0)
       }
     })
   }
@@ -95,4 +325,164 @@ mod tests {
     assert_eq!(cli.path, PathBuf::from("."));
     assert!(!cli.verbose);
   }
+
+  #[test_log::test]
+  fn should_parse_backup_dir() {
+    let cli = Cli::parse_from(["", "--backup-dir", "backup", "src"]);
+    assert_eq!(cli.path, PathBuf::from("src"));
+    assert_eq!(cli.backup_dir, Some("backup".to_string()));
+  }
+
+  #[test_log::test]
+  fn should_parse_keep_backup_on_clean() {
+    assert!(Cli::parse_from(["", "--keep-backup-on-clean"]).keep_backup_on_clean);
+    assert!(!Cli::parse_from(["", "src"]).keep_backup_on_clean);
+  }
+
+  #[test_log::test]
+  fn should_parse_scope() {
+    let cli = Cli::parse_from(["", "--scope", "common.dialog"]);
+    assert_eq!(cli.scope, Some("common.dialog".to_string()));
+  }
+
+  #[test_log::test]
+  fn should_map_changes_to_exit_code() {
+    assert_eq!(get_exit_code(CatalogChanges::Unchanged), 0);
+    assert_eq!(get_exit_code(CatalogChanges::Additions), 2);
+    assert_eq!(get_exit_code(CatalogChanges::Removals), 3);
+    assert_eq!(get_exit_code(CatalogChanges::Resets), 4);
+  }
+
+  #[test_log::test]
+  fn should_parse_init() {
+    assert_eq!(Cli::parse_from(["", "--init"]).init, Some(InitFormat::Json));
+    assert_eq!(Cli::parse_from(["", "--init", "yaml"]).init, Some(InitFormat::Yaml));
+    assert_eq!(Cli::parse_from(["", "src"]).init, None);
+  }
+
+  #[test_log::test]
+  fn should_parse_explain_globs() {
+    assert!(Cli::parse_from(["", "--explain-globs"]).explain_globs);
+    assert!(!Cli::parse_from(["", "src"]).explain_globs);
+  }
+
+  #[test_log::test]
+  fn should_profile_the_phases() {
+    let dir = tempdir::TempDir::new("cli").unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/app.ts"), "t('key', 'Value');").unwrap();
+    let cli = Cli::parse_from(["", "--profile", dir.path().to_str().unwrap()]);
+    assert!(cli.profile);
+
+    let mut profile = Profile::default();
+    cli.run_with_profile(&mut profile, std::io::empty()).unwrap();
+
+    let phases = profile.phases.iter().map(|phase| phase.name.as_str()).collect::<Vec<_>>();
+    assert_eq!(phases, vec!["Glob expansion", "Parsing", "Merging", "Writing"]);
+    assert!(dir.path().join("locales/en/translation.json").exists());
+  }
+
+  #[test_log::test]
+  fn should_parse_only_the_files_read_from_stdin() {
+    let dir = tempdir::TempDir::new("cli").unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    for name in ["first", "second", "third"] {
+      std::fs::write(dir.path().join(format!("src/{name}.ts")), format!("t('{name}');")).unwrap();
+    }
+    assert!(Cli::parse_from(["", "--stdin"]).stdin);
+
+    let stdin = std::io::Cursor::new("src/first.ts\n\nsrc/third.ts\nsrc/deleted.ts\n");
+    let files = read_file_list(stdin, dir.path()).unwrap();
+    assert_eq!(files, vec![dir.path().join("src/first.ts"), dir.path().join("src/third.ts")]);
+
+    let mut keys = parse_files(&files, Config::default()).into_iter().map(|entry| entry.key).collect::<Vec<_>>();
+    keys.sort();
+    assert_eq!(keys, vec!["first", "third"]);
+  }
+
+  #[test_log::test]
+  fn should_keep_the_keys_of_the_files_not_read_from_stdin() {
+    let dir = tempdir::TempDir::new("cli").unwrap();
+    std::fs::create_dir_all(dir.path().join("src")).unwrap();
+    for name in ["first", "second"] {
+      std::fs::write(dir.path().join(format!("src/{name}.ts")), format!("t('{name}', '{name}');")).unwrap();
+    }
+    let cli = Cli::parse_from(["", dir.path().to_str().unwrap()]);
+    cli.run_with_profile(&mut Profile::default(), std::io::empty()).unwrap();
+
+    std::fs::write(dir.path().join("src/first.ts"), "t('first', 'first'); t('added', 'added');").unwrap();
+    let cli = Cli::parse_from(["", "--stdin", dir.path().to_str().unwrap()]);
+    cli.run_with_profile(&mut Profile::default(), std::io::Cursor::new("src/first.ts\n")).unwrap();
+
+    let catalog = std::fs::read_to_string(dir.path().join("locales/en/translation.json")).unwrap();
+    let catalog: serde_json::Value = serde_json::from_str(&catalog).unwrap();
+    assert_eq!(catalog, serde_json::json!({ "added": "added", "first": "first", "second": "second" }));
+  }
+
+  #[test_log::test]
+  fn should_parse_merge_into() {
+    let cli = Cli::parse_from(["", "--merge-into", "catalogs.json"]);
+    assert_eq!(cli.merge_into, Some(PathBuf::from("catalogs.json")));
+  }
+
+  #[test_log::test]
+  fn should_parse_export_flat() {
+    let cli = Cli::parse_from(["", "--export-flat", "keys.json"]);
+    assert_eq!(cli.export_flat, Some(PathBuf::from("keys.json")));
+  }
+
+  #[test_log::test]
+  fn should_parse_export_pot() {
+    let cli = Cli::parse_from(["", "--export-pot", "messages.pot"]);
+    assert_eq!(cli.export_pot, Some(PathBuf::from("messages.pot")));
+  }
+
+  #[test_log::test]
+  fn should_parse_check() {
+    assert!(Cli::parse_from(["", "--check"]).check);
+    assert!(!Cli::parse_from(["", "src"]).check);
+  }
+
+  #[test_log::test]
+  fn should_report_the_updated_and_inconsistent_catalogs() {
+    let updated = || {
+      MergeResults {
+        locale: "en".into(),
+        namespace: "common".into(),
+        path: PathBuf::from("locales/en/common.json"),
+        unique_count: 1,
+        ..Default::default()
+      }
+    };
+    let unchanged = MergeResults { locale: "fr".into(), ..Default::default() };
+    let inconsistencies = vec![Inconsistency {
+      locale: "fr".into(),
+      namespace: "common".into(),
+      extra: vec!["old".into()],
+      missing: vec!["title".into()],
+    }];
+
+    let report = check_report(&[updated(), unchanged], &inconsistencies, &Config::default());
+    assert_eq!(report, vec![
+      "en:common would be updated (locales/en/common.json)",
+      "fr:common has the extra key old",
+      "fr:common is missing the key title",
+    ]);
+
+    let mut up_to_date = updated();
+    up_to_date.merged.merge_count = 1;
+    assert!(check_report(&[up_to_date], &[], &Config::default()).is_empty());
+  }
+
+  #[test_log::test]
+  fn should_parse_require_default() {
+    let cli = Cli::parse_from(["", "--require-default"]);
+    assert!(cli.require_default);
+  }
+
+  #[test_log::test]
+  fn should_parse_check_consistency() {
+    let cli = Cli::parse_from(["", "--check-consistency"]);
+    assert!(cli.check_consistency);
+  }
 }
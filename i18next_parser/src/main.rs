@@ -28,7 +28,7 @@ fn print_app() {
 }
 
 /// Entry point of the application
-fn main() -> color_eyre::Result<()> {
+fn main() -> color_eyre::Result<std::process::ExitCode> {
   use clap::Parser;
 
   use crate::{
@@ -37,23 +37,26 @@ fn main() -> color_eyre::Result<()> {
   };
   let cli = Cli::parse();
   if let Some(shell) = cli.generate_shell() {
-    generate_completion(shell)
+    generate_completion(shell).map(|_| std::process::ExitCode::SUCCESS)
   } else {
     print_app();
     initialize_panic_handler()?;
     initialize_logging(&cli.verbose)?;
     let instant = std::time::Instant::now();
-    cli.run().inspect(|_| {
-      use color_eyre::owo_colors::OwoColorize;
-      let elapsed = instant.elapsed().as_secs_f64() * 1000.0;
+    cli
+      .run()
+      .inspect(|_| {
+        use color_eyre::owo_colors::OwoColorize;
+        let elapsed = instant.elapsed().as_secs_f64() * 1000.0;
 
-      if elapsed < 100.0 {
-        eprintln!("Translations generated in {duration_str:.2}ms", duration_str = elapsed.green());
-      } else if elapsed < 500.0 {
-        eprintln!("Translations generated in {duration_str:.2}ms", duration_str = elapsed.yellow());
-      } else {
-        eprintln!("Translations generated in {duration_str:.2}ms", duration_str = elapsed.red().bold());
-      };
-    })
+        if elapsed < 100.0 {
+          eprintln!("Translations generated in {duration_str:.2}ms", duration_str = elapsed.green());
+        } else if elapsed < 500.0 {
+          eprintln!("Translations generated in {duration_str:.2}ms", duration_str = elapsed.yellow());
+        } else {
+          eprintln!("Translations generated in {duration_str:.2}ms", duration_str = elapsed.red().bold());
+        };
+      })
+      .map(std::process::ExitCode::from)
   }
 }
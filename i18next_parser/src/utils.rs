@@ -61,6 +61,8 @@ where
     let target = metadata.target();
     if target == "file_read" {
       write!(writer, "{} ", " [read] ".bright_green())?;
+    } else if target == "entry" {
+      write!(writer, "{} ", " [key ] ".bright_cyan())?;
     } else if target == "count" {
     } else {
       return Ok(());
@@ -90,7 +92,7 @@ where
     let level = *metadata.level();
 
     let target = metadata.target();
-    if target == "file_read" || target == "count" {
+    if target == "file_read" || target == "count" || target == "entry" {
       return Ok(());
     } else if target == "instrument_log" && cfg!(feature = "instrument") {
       write!(writer, "{} ", " [instr] ".bright_yellow())?;
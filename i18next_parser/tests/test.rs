@@ -56,6 +56,7 @@ fn should_parse_successfully() {
       backup: _backup,
       merged,
       old_catalog: _old_catalog,
+      ..
     } = entry;
 
     assert_eq!(merged.old_count, 0, "there isn't any values yet");
@@ -158,3 +159,124 @@ fn should_not_override_current_values() {
 
   drop(dir);
 }
+
+#[test]
+fn should_consume_namespace_separator_in_keys() {
+  let dir = TempDir::new("translations").unwrap();
+  let path = dir.path().join("src").join("main.tsx");
+  std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+  std::fs::write(&path, r#"const title = t("common:a.b", "Value");"#).unwrap();
+
+  let (working_path, config) = &setup_test(dir.path()).unwrap();
+  let entries = parse_directory(PathBuf::from(working_path), config).unwrap();
+  let entries = merge_all_values(entries, config).unwrap();
+
+  assert_eq!(entries.len(), 2, "one catalog per locale");
+  for MergeResults { namespace, locale, path, merged, .. } in entries {
+    assert_eq!(namespace, "common");
+    assert!(path.ends_with(["locales", locale.as_str(), "common.json"].join(MAIN_SEPARATOR_STR)));
+    assert_eq!(merged.new, json!({ "a": { "b": "Value" } }));
+  }
+  drop(dir);
+}
+
+#[test]
+fn should_restore_context_keys_of_trans_components() {
+  let dir = TempDir::new("translations").unwrap();
+  let path = dir.path().join("src").join("main.tsx");
+  std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+  std::fs::write(&path, r#"const el = <Trans i18nKey="friend">A friend</Trans>;"#).unwrap();
+  for locale in ["en", "fr"] {
+    let catalog = dir.path().join("locales").join(locale).join("translation.json");
+    create_file(catalog, &json!({ "friend": "A friend", "friend_male": "A boyfriend", "other_male": "Removed" }))
+      .unwrap();
+  }
+
+  let (working_path, config) = &setup_test(dir.path()).unwrap();
+  let entries = parse_directory(PathBuf::from(working_path), config).unwrap();
+  let entries = merge_all_values(entries, config).unwrap();
+
+  assert_eq!(entries.len(), 2, "one catalog per locale");
+  for MergeResults { merged, .. } in entries {
+    assert_eq!(merged.new, json!({ "friend": "A friend", "friend_male": "A boyfriend" }));
+    assert_eq!(merged.old, json!({ "other_male": "Removed" }));
+    assert_eq!(merged.pull_count, 1);
+  }
+  drop(dir);
+}
+
+#[test]
+fn should_exit_with_additions_code() {
+  let _ = initialize_logging(&false);
+  let dir = TempDir::new("translations").unwrap();
+  let path = dir.path().join("src").join("main.tsx");
+  std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+  std::fs::write(&path, r#"const title = t("title", "Title");"#).unwrap();
+  let config = Config {
+    locales: vec!["en".into()],
+    output: ["locales", "$LOCALE", "$NAMESPACE.json"].join(MAIN_SEPARATOR_STR),
+    input: vec!["**/*.{ts,tsx}".into()],
+    ..Default::default()
+  };
+  create_file(dir.path().join(".i18next-parser.json"), &config).unwrap();
+
+  let cli = Cli::parse_from(["", "--only-changed-exit-code", dir.path().to_str().unwrap()]);
+  assert_eq!(cli.run().unwrap(), 2, "the first run only adds keys");
+  assert_eq!(cli.run().unwrap(), 0, "the second run doesn't change anything");
+
+  std::fs::write(&path, r#"const title = t("other", "Other");"#).unwrap();
+  assert_eq!(cli.run().unwrap(), 3, "the title key has been removed");
+  drop(dir);
+}
+
+#[test]
+fn should_check_the_catalogs_without_writing_them() {
+  let _ = initialize_logging(&false);
+  let dir = TempDir::new("translations").unwrap();
+  let path = dir.path().join("src").join("main.tsx");
+  std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+  std::fs::write(&path, r#"const title = t("title", "Title");"#).unwrap();
+  let config = Config {
+    locales: vec!["en".into(), "fr".into()],
+    output: ["locales", "$LOCALE", "$NAMESPACE.json"].join(MAIN_SEPARATOR_STR),
+    input: vec!["**/*.{ts,tsx}".into()],
+    ..Default::default()
+  };
+  create_file(dir.path().join(".i18next-parser.json"), &config).unwrap();
+  Cli::parse_from(["", dir.path().to_str().unwrap()]).run().unwrap();
+
+  let check = Cli::parse_from(["", "--check", dir.path().to_str().unwrap()]);
+  assert_eq!(check.run().unwrap(), 0, "the catalogs are up to date");
+
+  std::fs::write(&path, r#"const title = t("title", "Title"); const other = t("other", "Other");"#).unwrap();
+  let error = check.run().unwrap_err();
+  assert_eq!(error.to_string(), "The check found 2 issues");
+
+  let en: Value =
+    serde_json::from_slice(&std::fs::read(dir.path().join("locales/en/translation.json")).unwrap()).unwrap();
+  assert_eq!(en, json!({ "title": "Title" }), "the catalogs are not written");
+  drop(dir);
+}
+
+#[test]
+fn should_not_write_the_catalogs_when_a_namespace_is_oversized() {
+  let _ = initialize_logging(&false);
+  let dir = TempDir::new("translations").unwrap();
+  let path = dir.path().join("src").join("main.tsx");
+  std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+  std::fs::write(&path, r#"const title = t("title", "Title"); const other = t("other", "Other");"#).unwrap();
+  let config = Config {
+    locales: vec!["en".into()],
+    output: ["locales", "$LOCALE", "$NAMESPACE.json"].join(MAIN_SEPARATOR_STR),
+    input: vec!["**/*.{ts,tsx}".into()],
+    max_keys_per_namespace: Some(1),
+    fail_on_warnings: true,
+    ..Default::default()
+  };
+  create_file(dir.path().join(".i18next-parser.json"), &config).unwrap();
+
+  let error = Cli::parse_from(["", dir.path().to_str().unwrap()]).run().unwrap_err();
+  assert_eq!(error.to_string(), "1 namespaces exceed the maximum number of keys");
+  assert!(!dir.path().join("locales/en/translation.json").exists(), "the catalogs are not written");
+  drop(dir);
+}
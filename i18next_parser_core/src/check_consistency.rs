@@ -0,0 +1,204 @@
+//! This module is responsible for reporting the keys that differ between the catalogs of the locales.
+use std::collections::{BTreeMap, BTreeSet};
+
+use color_eyre::{eyre::bail, owo_colors::OwoColorize};
+use log::warn;
+use serde_json::Value;
+
+use crate::{
+  config::{Config, PluralSuffixStyle},
+  helper::merge_hashes::PLURAL_SUFFIXES,
+  merger::merge_results::MergeResults,
+  transform::flatten_entries::flatten_value,
+};
+
+/// The keys of a catalog that differ from the catalog of the primary locale for the same namespace.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Inconsistency {
+  /// The locale of the catalog.
+  pub locale: String,
+  /// The namespace of the catalog.
+  pub namespace: String,
+  /// The dotted paths of the keys only found in the catalog.
+  pub extra: Vec<String>,
+  /// The dotted paths of the keys of the primary locale missing from the catalog.
+  pub missing: Vec<String>,
+}
+
+/// Lists the keys of a catalog, the plural forms being reduced to their key since each locale has its own.
+///
+/// The categories of `plural_overrides` are plural forms as well.
+fn catalog_keys(catalog: &Value, config: &Config) -> BTreeSet<String> {
+  let mut flat = BTreeMap::new();
+  flatten_value("", catalog, &mut flat);
+  let is_overridden = |suffix: &str| config.plural_overrides.values().flatten().any(|category| category == suffix);
+  flat
+    .into_keys()
+    .map(|key| {
+      let plural_key = key.rsplit_once(config.plural_separator.as_str()).filter(|(_, suffix)| {
+        is_overridden(suffix)
+          || match config.plural_suffix_style {
+            PluralSuffixStyle::Category => PLURAL_SUFFIXES.contains(suffix),
+            PluralSuffixStyle::Numeric => !suffix.is_empty() && suffix.chars().all(|c| c.is_ascii_digit()),
+          }
+      });
+      match plural_key {
+        Some((key, _)) => key.to_string(),
+        None => key,
+      }
+    })
+    .collect()
+}
+
+/// Lists the keys of the catalog of a result, the keys moved to `merged.old` being still part of the file read.
+fn result_keys(result: &MergeResults, config: &Config) -> BTreeSet<String> {
+  let mut keys = catalog_keys(&result.merged.new, config);
+  keys.extend(catalog_keys(&result.merged.old, config));
+  keys
+}
+
+/// Lists the keys of the merged catalogs that differ from the catalogs of the primary locale, the source locale.
+///
+/// The catalogs are compared as they were read, the keys removed by the merge being still listed.
+pub fn find_inconsistencies<C: AsRef<Config>>(merged: &[MergeResults], config: C) -> Vec<Inconsistency> {
+  let config = config.as_ref();
+  let Some(primary) = config.get_source_locale() else {
+    return vec![];
+  };
+
+  merged
+    .iter()
+    .filter(|result| &result.locale != primary)
+    .filter_map(|result| {
+      let primary_keys = merged
+        .iter()
+        .find(|primary_result| &primary_result.locale == primary && primary_result.namespace == result.namespace)
+        .map(|primary_result| result_keys(primary_result, config))
+        .unwrap_or_default();
+      let keys = result_keys(result, config);
+      let inconsistency = Inconsistency {
+        locale: result.locale.clone(),
+        namespace: result.namespace.clone(),
+        extra: keys.difference(&primary_keys).cloned().collect(),
+        missing: primary_keys.difference(&keys).cloned().collect(),
+      };
+      (!inconsistency.extra.is_empty() || !inconsistency.missing.is_empty()).then_some(inconsistency)
+    })
+    .collect()
+}
+
+/// Reports the keys that differ from the primary locale when `check_consistency` is enabled.
+///
+/// # Errors
+///
+/// This function returns an error if `check_consistency` is enabled and some catalogs are inconsistent.
+pub fn check_consistency<C: AsRef<Config>>(merged: &[MergeResults], config: C) -> color_eyre::Result<()> {
+  let config = config.as_ref();
+  if !config.check_consistency {
+    return Ok(());
+  }
+
+  let inconsistencies = find_inconsistencies(merged, config);
+  for inconsistency in &inconsistencies {
+    let catalog = format!("{}{}{}", inconsistency.locale, config.namespace_separator, inconsistency.namespace);
+    for key in &inconsistency.extra {
+      warn!("Key {} of {} is not in the primary locale", key.yellow(), catalog.bright_yellow());
+    }
+    for key in &inconsistency.missing {
+      warn!("Key {} of the primary locale is missing from {}", key.yellow(), catalog.bright_yellow());
+    }
+  }
+  if !inconsistencies.is_empty() {
+    bail!("{} catalogs are inconsistent with the primary locale", inconsistencies.len());
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+  use tempdir::TempDir;
+
+  use super::*;
+  use crate::{merge_all_values, merger::merge_results::merge_result, Entry};
+
+  #[test]
+  fn should_report_extra_key_of_secondary_locale() {
+    let merged = vec![
+      merge_result("translation", "en", json!({ "title": "Title", "items_one": "Item", "items_other": "Items" })),
+      merge_result(
+        "translation",
+        "fr",
+        json!({ "title": "Titre", "extra": "En trop", "items_one": "Objet", "items_many": "Objets", "items_other": "Objets" }),
+      ),
+    ];
+    let config = Config { locales: vec!["en".into(), "fr".into()], ..Default::default() };
+
+    assert_eq!(find_inconsistencies(&merged, &config), vec![Inconsistency {
+      locale: "fr".into(),
+      namespace: "translation".into(),
+      extra: vec!["extra".into()],
+      missing: vec![],
+    }]);
+  }
+
+  #[test]
+  fn should_reduce_the_overridden_plural_categories() {
+    let merged = vec![
+      merge_result("translation", "en", json!({ "items_one": "Item", "items_other": "Items" })),
+      merge_result(
+        "translation",
+        "fr",
+        json!({ "items_one": "Objet", "items_extra": "Objets", "items_other": "Objets" }),
+      ),
+    ];
+    let config = Config {
+      locales: vec!["en".into(), "fr".into()],
+      plural_overrides: HashMap::from([("fr".into(), vec!["one".into(), "extra".into(), "other".into()])]),
+      ..Default::default()
+    };
+
+    assert_eq!(find_inconsistencies(&merged, &config), vec![]);
+  }
+
+  #[test]
+  fn should_fail_on_inconsistency() {
+    let merged = vec![
+      merge_result("translation", "en", json!({ "title": "Title" })),
+      merge_result("translation", "fr", json!({})),
+    ];
+    let config = Config { locales: vec!["en".into(), "fr".into()], check_consistency: true, ..Default::default() };
+
+    assert!(check_consistency(&merged, &config).is_err());
+    assert!(check_consistency(&merged, Config { check_consistency: false, ..config }).is_ok());
+  }
+
+  #[test_log::test]
+  fn should_report_extra_key_of_secondary_locale_file() {
+    let dir = TempDir::new("check_consistency").unwrap();
+    let config = Config {
+      working_dir: dir.path().to_path_buf(),
+      locales: vec!["en".into(), "fr".into()],
+      output: "locales/$LOCALE/$NAMESPACE.json".into(),
+      check_consistency: true,
+      ..Default::default()
+    };
+    std::fs::create_dir_all(dir.path().join("locales/fr")).unwrap();
+    std::fs::write(dir.path().join("locales/fr/translation.json"), r#"{ "title": "Titre", "extra": "En trop" }"#)
+      .unwrap();
+
+    let merged = merge_all_values(vec![Entry::new_with_value("title", "Title")], &config).unwrap();
+
+    assert_eq!(find_inconsistencies(&merged, &config), vec![Inconsistency {
+      locale: "fr".into(),
+      namespace: "translation".into(),
+      extra: vec!["extra".into()],
+      missing: vec![],
+    }]);
+    assert!(check_consistency(&merged, &config).is_err());
+  }
+}
@@ -0,0 +1,96 @@
+//! This module is responsible for reporting the keys extracted without a default value.
+use color_eyre::{eyre::bail, owo_colors::OwoColorize};
+use log::warn;
+
+use crate::{config::Config, Entry};
+
+/// Lists the keys of the entries extracted without any default value.
+///
+/// Keys belonging to a namespace are prefixed with it, using the configured namespace separator.
+pub fn find_missing_default_values<C: AsRef<Config>>(entries: &[Entry], config: C) -> Vec<String> {
+  let config = config.as_ref();
+  let mut keys = entries
+    .iter()
+    .filter(|entry| entry.value.as_ref().is_none_or(|value| value.is_empty()))
+    .map(|entry| {
+      match &entry.namespace {
+        Some(namespace) => format!("{}{}{}", namespace, config.namespace_separator, entry.key),
+        None => entry.key.clone(),
+      }
+    })
+    .collect::<Vec<_>>();
+  keys.sort();
+  keys.dedup();
+  keys
+}
+
+/// Reports the keys extracted without any default value when `require_default` is enabled.
+///
+/// # Errors
+///
+/// This function returns an error if some keys don't have a default value and `fail_on_warnings` is enabled.
+pub fn check_default_values<C: AsRef<Config>>(entries: &[Entry], config: C) -> color_eyre::Result<Vec<String>> {
+  let config = config.as_ref();
+  if !config.require_default {
+    return Ok(vec![]);
+  }
+
+  let keys = find_missing_default_values(entries, config);
+  for key in &keys {
+    warn!("Key extracted without a default value: {}", key.yellow());
+  }
+  if config.fail_on_warnings && !keys.is_empty() {
+    bail!("{} keys have been extracted without a default value", keys.len());
+  }
+
+  Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn should_list_keys_without_default_value() {
+    let entries = vec![
+      Entry::new_with_value("with.value", "Value"),
+      Entry::empty("without.value"),
+      Entry::new_with_ns("without.value", "ns"),
+      Entry::new_with_value("empty.value", ""),
+    ];
+    let config = Config { require_default: true, ..Default::default() };
+
+    let keys = check_default_values(&entries, &config).unwrap();
+
+    assert_eq!(keys, vec!["empty.value", "ns:without.value", "without.value"]);
+  }
+
+  #[test]
+  fn should_pass_when_all_keys_have_default_value() {
+    let entries = vec![Entry::new_with_value("key1", "Value 1"), Entry::new("key2", "Value 2", "ns")];
+    let config = Config { require_default: true, fail_on_warnings: true, ..Default::default() };
+
+    let keys = check_default_values(&entries, &config).unwrap();
+
+    assert!(keys.is_empty());
+  }
+
+  #[test]
+  fn should_fail_on_missing_default_value_when_failing_on_warnings() {
+    let entries = vec![Entry::empty("key")];
+    let config = Config { require_default: true, fail_on_warnings: true, ..Default::default() };
+
+    assert!(check_default_values(&entries, &config).is_err());
+  }
+
+  #[test]
+  fn should_not_report_when_disabled() {
+    let entries = vec![Entry::empty("key")];
+
+    let keys = check_default_values(&entries, Config::default()).unwrap();
+
+    assert!(keys.is_empty());
+  }
+}
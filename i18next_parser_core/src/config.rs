@@ -1,9 +1,17 @@
 //! This module provides configuration for the i18n system.
-use std::path::{PathBuf, MAIN_SEPARATOR_STR};
+use std::{
+  collections::HashMap,
+  path::{Path, PathBuf, MAIN_SEPARATOR_STR},
+  sync::LazyLock,
+};
 
-use color_eyre::owo_colors::OwoColorize;
+use color_eyre::{
+  eyre::{bail, eyre},
+  owo_colors::OwoColorize,
+};
 use config::{File, FileFormat, FileSourceFile};
-use serde::{Deserialize, Serialize};
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize};
 
 /// Line ending configuration.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -32,21 +40,238 @@ impl From<LineEnding> for config::Value {
     }
   }
 }
+/// Interpolation escaping configuration for extracted default values.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum InterpolationEscape {
+  /// Keep interpolations as written.
+  #[default]
+  Keep,
+  /// Write interpolations escaped (`{{value}}`).
+  Escaped,
+  /// Write interpolations unescaped (`{{- value}}`).
+  Unescaped,
+}
+
+/// Convert `InterpolationEscape` to `config::Value`.
+impl From<InterpolationEscape> for config::Value {
+  /// Convert `InterpolationEscape` to `config::Value`.
+  #[inline]
+  fn from(val: InterpolationEscape) -> Self {
+    match val {
+      InterpolationEscape::Keep => "keep".into(),
+      InterpolationEscape::Escaped => "escaped".into(),
+      InterpolationEscape::Unescaped => "unescaped".into(),
+    }
+  }
+}
+
+/// Catalog output format configuration.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum OutputFormat {
+  /// Write the catalog as JSON.
+  #[default]
+  Json,
+  /// Write the catalog as YAML.
+  Yaml,
+}
+
+impl OutputFormat {
+  /// Get the file extension of the catalogs written in this format.
+  pub fn extension(&self) -> &'static str {
+    match self {
+      OutputFormat::Json => "json",
+      OutputFormat::Yaml => "yml",
+    }
+  }
+}
+
+/// Convert `OutputFormat` to `config::Value`.
+impl From<OutputFormat> for config::Value {
+  /// Convert `OutputFormat` to `config::Value`.
+  #[inline]
+  fn from(val: OutputFormat) -> Self {
+    match val {
+      OutputFormat::Json => "json".into(),
+      OutputFormat::Yaml => "yaml".into(),
+    }
+  }
+}
+
+/// Catalog keys sort order configuration.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum SortOrder {
+  /// Sort the keys character by character (`item10` before `item2`).
+  #[default]
+  Lexicographic,
+  /// Sort the digits of the keys by their numeric value (`item2` before `item10`).
+  Natural,
+}
+
+/// Convert `SortOrder` to `config::Value`.
+impl From<SortOrder> for config::Value {
+  /// Convert `SortOrder` to `config::Value`.
+  #[inline]
+  fn from(val: SortOrder) -> Self {
+    match val {
+      SortOrder::Lexicographic => "lexicographic".into(),
+      SortOrder::Natural => "natural".into(),
+    }
+  }
+}
+
+/// Handling of the keys and namespaces that cannot be resolved statically.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum OnUnresolved {
+  /// Skip the keys and ignore the namespaces that cannot be resolved.
+  #[default]
+  Skip,
+  /// Replace the keys and namespaces that cannot be resolved by `__UNRESOLVED__`.
+  Placeholder,
+}
+
+/// Convert `OnUnresolved` to `config::Value`.
+impl From<OnUnresolved> for config::Value {
+  /// Convert `OnUnresolved` to `config::Value`.
+  #[inline]
+  fn from(val: OnUnresolved) -> Self {
+    match val {
+      OnUnresolved::Skip => "skip".into(),
+      OnUnresolved::Placeholder => "placeholder".into(),
+    }
+  }
+}
+
+/// Plural suffix style configuration.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub enum PluralSuffixStyle {
+  /// Use the plural categories as suffixes (`key_one`, `key_other`).
+  #[default]
+  Category,
+  /// Use the numeric plural indices as suffixes (`key_0`, `key_1`).
+  Numeric,
+}
+
+/// Convert `PluralSuffixStyle` to `config::Value`.
+impl From<PluralSuffixStyle> for config::Value {
+  /// Convert `PluralSuffixStyle` to `config::Value`.
+  #[inline]
+  fn from(val: PluralSuffixStyle) -> Self {
+    match val {
+      PluralSuffixStyle::Category => "category".into(),
+      PluralSuffixStyle::Numeric => "numeric".into(),
+    }
+  }
+}
+
+/// The configuration files looked up in the working directory, in loading order.
+const CONFIG_FILES: [(&str, FileFormat); 11] = [
+  ("i18next-parser.config.json", FileFormat::Json),
+  (".i18next-parser.json5", FileFormat::Json5),
+  (".i18next-parser.json", FileFormat::Json),
+  (".i18next-parser.yaml", FileFormat::Yaml),
+  (".i18next-parser.toml", FileFormat::Toml),
+  (".i18next-parser.ini", FileFormat::Ini),
+  ("i18next-parser.json5", FileFormat::Json5),
+  ("i18next-parser.json", FileFormat::Json),
+  ("i18next-parser.yaml", FileFormat::Yaml),
+  ("i18next-parser.toml", FileFormat::Toml),
+  ("i18next-parser.ini", FileFormat::Ini),
+];
+
+/// The `package.json` field holding the configuration, below the dedicated configuration files.
+const PACKAGE_JSON_FIELD: &str = "i18next-parser";
+
+/// The tokens supported in the output of the configuration.
+const OUTPUT_TOKENS: [&str; 3] = ["$BASE", "$LOCALE", "$NAMESPACE"];
+
+/// Matches the `$TOKEN`s of the output of the configuration.
+static TOKEN_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\$[A-Za-z_]+").unwrap());
+
+/// Read the configuration stored in the `i18next-parser` field of the `package.json` of the working directory.
+///
+/// # Returns
+/// The configuration as a JSON string, or `None` if there is no `package.json` or no configuration in it.
+fn read_package_json_config(working_dir: &Path) -> Option<String> {
+  let path = working_dir.join("package.json");
+  let content = std::fs::read_to_string(&path).ok()?;
+  let package = match serde_json::from_str::<serde_json::Value>(&content) {
+    Ok(package) => package,
+    Err(err) => {
+      log::warn!("Unable to parse {}: {err}", path.display().yellow());
+      return None;
+    },
+  };
+  package.get(PACKAGE_JSON_FIELD).filter(|config| config.is_object()).map(|config| config.to_string())
+}
+
+/// Format of the configuration file created by `Config::init`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ConfigFileFormat {
+  /// Write a `.i18next-parser.json` file.
+  #[default]
+  Json,
+  /// Write a commented `.i18next-parser.json5` file.
+  Json5,
+  /// Write a commented `.i18next-parser.yaml` file.
+  Yaml,
+}
+
+impl ConfigFileFormat {
+  /// Get the name of the configuration file for the format.
+  pub fn file_name(&self) -> &'static str {
+    match self {
+      ConfigFileFormat::Json => ".i18next-parser.json",
+      ConfigFileFormat::Json5 => ".i18next-parser.json5",
+      ConfigFileFormat::Yaml => ".i18next-parser.yaml",
+    }
+  }
+}
+
+/// Deserialize a list of strings from either a single string or a list of strings.
+fn deserialize_string_or_vec<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+  #[derive(Deserialize)]
+  #[serde(untagged)]
+  enum StringOrVec {
+    String(String),
+    Vec(Vec<String>),
+  }
+
+  Ok(match StringOrVec::deserialize(deserializer)? {
+    StringOrVec::String(value) => vec![value],
+    StringOrVec::Vec(values) => values,
+  })
+}
+
 /// This struct represents the configuration for the i18n system.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
   /// The working directory for the i18n system.
   pub working_dir: PathBuf,
   /// A vector of locales used in the i18n system.
+  #[serde(deserialize_with = "deserialize_string_or_vec")]
   pub locales: Vec<String>,
+  /// An optional locale used as source of truth instead of the first of the locales in the i18n system.
+  pub source_locale: Option<String>,
+  /// An optional `$LOCALE` path to JSON catalogs by namespace, providing the missing default values in the i18n system.
+  pub seed: Option<PathBuf>,
   /// A vector of input sources for the i18n system.
+  #[serde(deserialize_with = "deserialize_string_or_vec")]
   pub input: Vec<String>,
   /// The output destination for the i18n system.
   pub output: String,
   /// The separator used in the context of the i18n system.
   pub context_separator: String,
-  /// A boolean indicating whether to create old catalogs in the i18n system.
+  /// A boolean indicating whether to archive the removed keys to old catalogs in the i18n system.
   pub create_old_catalogs: bool,
+  /// A boolean indicating whether to archive the removed keys to old catalogs, even without `create_old_catalogs`, in
+  /// the i18n system.
+  pub keep_backup_on_clean: bool,
+  /// The suffix appended to the file name of old catalogs in the i18n system.
+  pub old_catalog_suffix: String,
+  /// An optional directory, relative to each catalog, where old catalogs are written in the i18n system.
+  pub old_catalog_dir: Option<String>,
+  /// An optional directory, relative to the working directory, gathering every old catalog in the i18n system.
+  pub backup_dir: Option<String>,
   /// The default namespace used in the i18n system.
   pub default_namespace: String,
   /// The default value used in the i18n system.
@@ -57,18 +282,71 @@ pub struct Config {
   pub key_separator: String,
   /// The line ending configuration for the i18n system.
   pub line_ending: LineEnding,
+  /// A boolean indicating whether to start the catalogs with the UTF-8 byte order mark in the i18n system.
+  pub byte_order_mark: bool,
+  /// The interpolation escaping applied to default values in the i18n system.
+  pub interpolation_escape: InterpolationEscape,
   /// The separator used for namespaces in the i18n system.
   pub namespace_separator: String,
   /// The separator used for plurals in the i18n system.
   pub plural_separator: String,
+  /// The style of the plural suffixes in the i18n system.
+  pub plural_suffix_style: PluralSuffixStyle,
   /// A boolean indicating whether to sort entries in the i18n system.
   pub sort: bool,
+  /// The order of the keys when sorting entries in the i18n system.
+  pub sort_order: SortOrder,
+  /// A boolean indicating whether to sort the plural and context variants next to their key in the i18n system.
+  pub group_variants: bool,
   /// A boolean indicating whether to output verbose logs in the i18n system.
   pub verbose: bool,
   /// A boolean indicating whether to fail on warnings in the i18n system.
   pub fail_on_warnings: bool,
   /// A boolean indicating whether to fail on updates in the i18n system.
   pub fail_on_update: bool,
+  /// A boolean indicating whether every key requires a default value in the i18n system.
+  pub require_default: bool,
+  /// A boolean indicating whether every locale must have the same keys as the primary locale in the i18n system.
+  pub check_consistency: bool,
+  /// A boolean indicating whether `Trans` components without `i18nKey` use their children as key in the i18n system.
+  pub trans_key_from_children: bool,
+  /// The functions (besides `t`), named `name` or `object.name`, whose first argument is a key to extract without
+  /// default value in the i18n system.
+  pub key_functions: Vec<String>,
+  /// A boolean indicating whether the catalogs can be written outside of the working directory in the i18n system.
+  pub allow_outside_output: bool,
+  /// A boolean indicating whether to create the missing directories of the catalogs in the i18n system.
+  pub create_dirs: bool,
+  /// A boolean indicating whether to fail when the input globs match no source file in the i18n system.
+  pub error_on_no_input: bool,
+  /// A boolean indicating whether to use the file name as namespace of keys without one in the i18n system.
+  pub namespace_from_filename: bool,
+  /// A boolean indicating whether to read the constants imported from other modules in the i18n system.
+  pub resolve_imports: bool,
+  /// The handling of the keys and namespaces that cannot be resolved in the i18n system.
+  pub on_unresolved: OnUnresolved,
+  /// The output format of the namespaces written in another format than the `output` one in the i18n system.
+  #[serde(default)]
+  pub namespace_formats: HashMap<String, OutputFormat>,
+  /// An optional comment written at the top of the JSON5 and YAML catalogs in the i18n system.
+  pub file_header: Option<String>,
+  /// The plural categories used instead of the computed rules of their locale in the i18n system.
+  #[serde(default)]
+  pub plural_overrides: HashMap<String, Vec<String>>,
+  /// A boolean indicating whether to trim the default values in the i18n system.
+  pub trim_default_values: bool,
+  /// A boolean indicating whether to unescape the doubly escaped sequences, like `\\n`, of the keys in the i18n system.
+  pub unescape_defaults: bool,
+  /// A boolean indicating whether empty default values overwrite existing values in the i18n system.
+  pub allow_empty_default: bool,
+  /// A boolean indicating whether to warn about keys equal to their default value in the i18n system.
+  pub warn_on_key_equals_value: bool,
+  /// An optional number representing the maximum nesting depth of a key in the i18n system.
+  pub max_key_depth: Option<usize>,
+  /// An optional number representing the maximum number of keys of a merged namespace in the i18n system.
+  pub max_keys_per_namespace: Option<usize>,
+  /// An optional key path, the only subtree of the catalogs updated in the i18n system.
+  pub scope: Option<String>,
   /// An optional string representing the locale to reset the default value in the i18n system.
   pub reset_default_value_locale: Option<String>,
 }
@@ -85,6 +363,8 @@ impl Default for Config {
     Self {
       working_dir: PathBuf::from("."),
       locales: vec!["en".into()],
+      source_locale: Default::default(),
+      seed: Default::default(),
       output: ["locales", "$LOCALE", "$NAMESPACE.json"].join(MAIN_SEPARATOR_STR),
       input: vec!["src/**/*.{ts,tsx}".into()],
       context_separator: "_".into(),
@@ -93,13 +373,42 @@ impl Default for Config {
       keep_removed: Default::default(),
       key_separator: ".".into(),
       line_ending: LineEnding::Auto,
+      byte_order_mark: Default::default(),
+      interpolation_escape: InterpolationEscape::Keep,
       namespace_separator: ":".into(),
       plural_separator: "_".into(),
+      plural_suffix_style: PluralSuffixStyle::Category,
       sort: true,
+      sort_order: SortOrder::Lexicographic,
+      group_variants: Default::default(),
       verbose: Default::default(),
       create_old_catalogs: Default::default(),
+      keep_backup_on_clean: Default::default(),
+      old_catalog_suffix: "_old".into(),
+      old_catalog_dir: Default::default(),
+      backup_dir: Default::default(),
       fail_on_warnings: Default::default(),
       fail_on_update: Default::default(),
+      require_default: Default::default(),
+      check_consistency: Default::default(),
+      trans_key_from_children: Default::default(),
+      key_functions: vec!["i18n.exists".into()],
+      allow_outside_output: Default::default(),
+      create_dirs: true,
+      error_on_no_input: true,
+      namespace_from_filename: Default::default(),
+      resolve_imports: Default::default(),
+      on_unresolved: OnUnresolved::Skip,
+      namespace_formats: Default::default(),
+      file_header: Default::default(),
+      plural_overrides: Default::default(),
+      trim_default_values: true,
+      unescape_defaults: true,
+      allow_empty_default: Default::default(),
+      warn_on_key_equals_value: Default::default(),
+      max_key_depth: Default::default(),
+      max_keys_per_namespace: Default::default(),
+      scope: Default::default(),
       reset_default_value_locale: Default::default(),
     }
   }
@@ -111,6 +420,10 @@ impl Config {
   /// # Arguments
   /// * `working_dir` - The working directory for the i18n system.
   /// * `verbose` - A boolean indicating whether to output verbose logs in the i18n system.
+  ///
+  /// # Errors
+  /// This function returns an error if the configuration cannot be read, or if the `source_locale` is not one of the
+  /// `locales`.
   pub fn new<T>(working_dir: T, verbose: bool) -> Result<Self, config::ConfigError>
   where
     T: Into<PathBuf>,
@@ -128,34 +441,47 @@ impl Config {
       .set_default("keep_removed", default_config.keep_removed)?
       .set_default("key_separator", default_config.key_separator)?
       .set_default("line_ending", default_config.line_ending)?
+      .set_default("byte_order_mark", default_config.byte_order_mark)?
+      .set_default("interpolation_escape", default_config.interpolation_escape)?
       .set_default("namespace_separator", default_config.namespace_separator)?
       .set_default("plural_separator", default_config.plural_separator)?
+      .set_default("plural_suffix_style", default_config.plural_suffix_style)?
       .set_default("create_old_catalogs", default_config.create_old_catalogs)?
+      .set_default("keep_backup_on_clean", default_config.keep_backup_on_clean)?
+      .set_default("old_catalog_suffix", default_config.old_catalog_suffix)?
       .set_default("sort", default_config.sort)?
+      .set_default("sort_order", default_config.sort_order)?
+      .set_default("group_variants", default_config.group_variants)?
       .set_default("verbose", default_config.verbose)?
       .set_default("fail_on_warnings", default_config.fail_on_warnings)?
       .set_default("fail_on_update", default_config.fail_on_update)?
+      .set_default("require_default", default_config.require_default)?
+      .set_default("check_consistency", default_config.check_consistency)?
+      .set_default("trim_default_values", default_config.trim_default_values)?
+      .set_default("unescape_defaults", default_config.unescape_defaults)?
+      .set_default("allow_empty_default", default_config.allow_empty_default)?
+      .set_default("warn_on_key_equals_value", default_config.warn_on_key_equals_value)?
+      .set_default("namespace_from_filename", default_config.namespace_from_filename)?
+      .set_default("resolve_imports", default_config.resolve_imports)?
+      .set_default("on_unresolved", default_config.on_unresolved)?
+      .set_default("allow_outside_output", default_config.allow_outside_output)?
+      .set_default("create_dirs", default_config.create_dirs)?
+      .set_default("error_on_no_input", default_config.error_on_no_input)?
+      .set_default("key_functions", default_config.key_functions)?
+      .set_default("trans_key_from_children", default_config.trans_key_from_children)?
       .set_override("working_dir", working_dir_opt)?;
 
     if verbose {
       builder = builder.set_override("verbose", true)?;
     }
 
-    let config_files = [
-      (".i18next-parser.json5", FileFormat::Json5),
-      (".i18next-parser.json", FileFormat::Json),
-      (".i18next-parser.yaml", FileFormat::Yaml),
-      (".i18next-parser.toml", FileFormat::Toml),
-      (".i18next-parser.ini", FileFormat::Ini),
-      ("i18next-parser.json5", FileFormat::Json5),
-      ("i18next-parser.json", FileFormat::Json),
-      ("i18next-parser.yaml", FileFormat::Yaml),
-      ("i18next-parser.toml", FileFormat::Toml),
-      ("i18next-parser.ini", FileFormat::Ini),
-    ];
-
     let mut found_config = false;
-    for (file, format) in &config_files {
+    if let Some(package_config) = read_package_json_config(&working_dir) {
+      found_config = true;
+      log::info!("found {} in {}", PACKAGE_JSON_FIELD.italic().green(), "package.json".yellow());
+      builder = builder.add_source(File::from_str(&package_config, FileFormat::Json));
+    }
+    for (file, format) in &CONFIG_FILES {
       log::trace!("Looking for {} in {}", file.italic().yellow(), working_dir.display().yellow());
       let file_name = &working_dir.join(file);
       let config_file: File<FileSourceFile, FileFormat> = file_name.clone().into();
@@ -171,14 +497,86 @@ impl Config {
       log::warn!("No configuration file found. Using default configuration.");
     }
 
-    let configuration = builder.build().and_then(|config| config.try_deserialize())?;
+    let configuration: Config = builder.build().and_then(|config| config.try_deserialize())?;
     log::trace!("Loaded configuration: {:#?}", configuration);
+    if let Some(source_locale) = configuration.source_locale.as_ref() {
+      if !configuration.locales.contains(source_locale) {
+        return Err(config::ConfigError::Message(format!(
+          "The source locale {source_locale:?} is not one of the locales {:?}",
+          configuration.locales
+        )));
+      }
+    }
     Ok(configuration)
   }
 
+  /// Create a configuration file holding the default configuration in the working directory.
+  ///
+  /// # Arguments
+  /// * `working_dir` - The working directory for the i18n system.
+  /// * `format` - The format of the configuration file to create.
+  ///
+  /// # Errors
+  /// This function returns an error if a configuration file already exists in the working directory.
+  pub fn init<T: Into<PathBuf>>(working_dir: T, format: ConfigFileFormat) -> color_eyre::Result<PathBuf> {
+    let working_dir: PathBuf = working_dir.into();
+    if let Some((file, _)) = CONFIG_FILES.iter().find(|(file, _)| working_dir.join(file).exists()) {
+      bail!("A configuration file already exists: {}", working_dir.join(file).display());
+    }
+
+    let mut value = serde_json::to_value(Config::default())?;
+    if let Some(map) = value.as_object_mut() {
+      map.remove("working_dir");
+    }
+    const HEADER: &str =
+      "Default configuration of i18next-parser, every option can be removed to use its default value";
+    let content = match format {
+      ConfigFileFormat::Json => serde_json::to_string_pretty(&value)?,
+      ConfigFileFormat::Json5 => format!("// {HEADER}\n{}", serde_json::to_string_pretty(&value)?),
+      ConfigFileFormat::Yaml => format!("# {HEADER}\n{}", serde_yaml_ng::to_string(&value)?),
+    };
+
+    let path = working_dir.join(format.file_name());
+    std::fs::write(&path, content)?;
+    log::info!("Created {}", path.display().yellow());
+    Ok(path)
+  }
+
   /// Get the output destination for the i18n system.
-  pub fn get_output(&self) -> String {
-    self.working_dir.join(&self.output).to_str().unwrap().to_string()
+  ///
+  /// The `$BASE` token is replaced by the working directory, and the `$LOCALE` and `$NAMESPACE` tokens are kept to be
+  /// replaced for each catalog.
+  ///
+  /// # Errors
+  ///
+  /// Returns an error when the output contains any other `$TOKEN`.
+  pub fn get_output(&self) -> color_eyre::Result<String> {
+    let unknown_tokens = TOKEN_REGEX
+      .find_iter(&self.output)
+      .map(|token| token.as_str())
+      .filter(|token| !OUTPUT_TOKENS.contains(token))
+      .collect::<Vec<_>>();
+    if !unknown_tokens.is_empty() {
+      bail!(
+        "Unknown tokens {} in the output {}, the supported ones are {}",
+        unknown_tokens.join(", "),
+        self.output,
+        OUTPUT_TOKENS.join(", ")
+      );
+    }
+
+    let working_dir = self.working_dir.to_str().ok_or(eyre!("Invalid working directory {:?}", self.working_dir))?;
+    let output = if self.output.contains("$BASE") {
+      PathBuf::from(self.output.replace("$BASE", working_dir))
+    } else {
+      self.working_dir.join(&self.output)
+    };
+    Ok(output.to_str().ok_or(eyre!("Invalid output {output:?}"))?.to_string())
+  }
+
+  /// Get the source locale for the i18n system, the `source_locale` or else the first of the `locales`.
+  pub fn get_source_locale(&self) -> Option<&String> {
+    self.source_locale.as_ref().or(self.locales.first())
   }
 }
 
@@ -213,6 +611,49 @@ mod tests {
     let value: config::Value = line_ending.into();
     assert_eq!(value, "lf".into());
   }
+
+  #[test_log::test]
+  fn test_interpolation_escape() {
+    let value: config::Value = InterpolationEscape::Keep.into();
+    assert_eq!(value, "keep".into());
+    let value: config::Value = InterpolationEscape::Escaped.into();
+    assert_eq!(value, "escaped".into());
+    let value: config::Value = InterpolationEscape::Unescaped.into();
+    assert_eq!(value, "unescaped".into());
+  }
+
+  #[test_log::test]
+  fn test_output_format() {
+    let value: config::Value = OutputFormat::Json.into();
+    assert_eq!(value, "json".into());
+    let value: config::Value = OutputFormat::Yaml.into();
+    assert_eq!(value, "yaml".into());
+    assert_eq!(OutputFormat::Yaml.extension(), "yml");
+  }
+
+  #[test_log::test]
+  fn test_plural_suffix_style() {
+    let value: config::Value = PluralSuffixStyle::Category.into();
+    assert_eq!(value, "category".into());
+    let value: config::Value = PluralSuffixStyle::Numeric.into();
+    assert_eq!(value, "numeric".into());
+  }
+
+  #[test_log::test]
+  fn test_sort_order() {
+    let value: config::Value = SortOrder::Lexicographic.into();
+    assert_eq!(value, "lexicographic".into());
+    let value: config::Value = SortOrder::Natural.into();
+    assert_eq!(value, "natural".into());
+  }
+
+  #[test_log::test]
+  fn test_on_unresolved() {
+    let value: config::Value = OnUnresolved::Skip.into();
+    assert_eq!(value, "skip".into());
+    let value: config::Value = OnUnresolved::Placeholder.into();
+    assert_eq!(value, "placeholder".into());
+  }
 }
 #[cfg(test)]
 mod config_tests {
@@ -236,6 +677,7 @@ mod config_tests {
     let config = Config::new(working_dir, verbose).unwrap();
     assert_eq!(config.working_dir, PathBuf::from(working_dir));
     assert!(config.verbose);
+    assert_eq!(config.interpolation_escape, InterpolationEscape::Keep);
   }
 
   #[test_log::test]
@@ -246,7 +688,138 @@ mod config_tests {
       ..Config::default()
     };
     let expected_output = "/tmp/locales/$LOCALE/$NAMESPACE.json";
-    assert_eq!(config.get_output(), expected_output);
+    assert_eq!(config.get_output().unwrap(), expected_output);
+  }
+
+  #[test_log::test]
+  fn config_get_source_locale_defaults_to_the_first_locale() {
+    let config = Config { locales: vec!["en".into(), "fr".into()], ..Config::default() };
+    assert_eq!(config.get_source_locale().map(String::as_str), Some("en"));
+
+    let config = Config { source_locale: Some("fr".into()), ..config };
+    assert_eq!(config.get_source_locale().map(String::as_str), Some("fr"));
+  }
+
+  #[test_log::test]
+  fn config_get_output_replaces_base_token() {
+    let config = Config {
+      working_dir: PathBuf::from("/tmp"),
+      output: "$BASE/locales/$LOCALE/$NAMESPACE.json".to_string(),
+      ..Config::default()
+    };
+    assert_eq!(config.get_output().unwrap(), "/tmp/locales/$LOCALE/$NAMESPACE.json");
+  }
+
+  #[test_log::test]
+  fn config_get_output_rejects_unknown_token() {
+    let config = Config { output: "locales/$LANG/$NAMESPACE.$FORMAT".to_string(), ..Config::default() };
+    let error = config.get_output().unwrap_err().to_string();
+    assert!(error.contains("$LANG, $FORMAT"), "{error}");
+  }
+
+  #[test_log::test]
+  fn config_new_accepts_scalar_input_and_locales() {
+    let dir = tempdir::TempDir::new("config").unwrap();
+    std::fs::write(dir.path().join(".i18next-parser.json"), r#"{ "input": "src/**/*.tsx", "locales": "fr" }"#).unwrap();
+
+    let config = Config::new(dir.path(), false).unwrap();
+    assert_eq!(config.input, vec!["src/**/*.tsx"]);
+    assert_eq!(config.locales, vec!["fr"]);
+  }
+
+  #[test_log::test]
+  fn config_new_accepts_list_input_and_locales() {
+    let dir = tempdir::TempDir::new("config").unwrap();
+    std::fs::write(
+      dir.path().join(".i18next-parser.json"),
+      r#"{ "input": ["src/**/*.ts", "src/**/*.tsx"], "locales": ["en", "fr"] }"#,
+    )
+    .unwrap();
+
+    let config = Config::new(dir.path(), false).unwrap();
+    assert_eq!(config.input, vec!["src/**/*.ts", "src/**/*.tsx"]);
+    assert_eq!(config.locales, vec!["en", "fr"]);
+  }
+
+  #[test_log::test]
+  fn config_init_writes_the_default_configuration() {
+    for format in [ConfigFileFormat::Json, ConfigFileFormat::Json5, ConfigFileFormat::Yaml] {
+      let dir = tempdir::TempDir::new("config").unwrap();
+
+      let path = Config::init(dir.path(), format).unwrap();
+      assert_eq!(path, dir.path().join(format.file_name()));
+      let content = std::fs::read_to_string(&path).unwrap();
+      assert!(content.contains("default_namespace"), "{content}");
+      assert!(content.contains("translation"), "{content}");
+      assert!(!content.contains("working_dir"), "{content}");
+
+      let config = Config::new(dir.path(), false).unwrap();
+      let default = Config::default();
+      assert_eq!(config.locales, default.locales);
+      assert_eq!(config.input, default.input);
+      assert_eq!(config.output, default.output);
+      assert_eq!(config.default_namespace, default.default_namespace);
+    }
+  }
+
+  #[test_log::test]
+  fn config_new_reads_the_package_json_field() {
+    let dir = tempdir::TempDir::new("config").unwrap();
+    std::fs::write(
+      dir.path().join("package.json"),
+      r#"{ "name": "app", "i18next-parser": { "locales": ["fr", "nl"], "default_namespace": "common" } }"#,
+    )
+    .unwrap();
+
+    let config = Config::new(dir.path(), false).unwrap();
+    assert_eq!(config.locales, vec!["fr", "nl"]);
+    assert_eq!(config.default_namespace, "common");
+
+    std::fs::write(dir.path().join("i18next-parser.json"), r#"{ "locales": ["de"] }"#).unwrap();
+    let config = Config::new(dir.path(), false).unwrap();
+    assert_eq!(config.locales, vec!["de"]);
+    assert_eq!(config.default_namespace, "common");
+  }
+
+  #[test_log::test]
+  fn config_new_discovers_the_config_json_sidecar() {
+    let dir = tempdir::TempDir::new("config").unwrap();
+    std::fs::write(dir.path().join("i18next-parser.config.json"), r#"{ "locales": ["fr"], "sort": false }"#).unwrap();
+
+    let config = Config::new(dir.path(), false).unwrap();
+    assert_eq!(config.locales, vec!["fr"]);
+    assert!(!config.sort);
+  }
+
+  #[test_log::test]
+  fn config_new_rejects_a_source_locale_outside_the_locales() {
+    let dir = tempdir::TempDir::new("config").unwrap();
+    std::fs::write(dir.path().join("i18next-parser.json"), r#"{ "locales": ["en", "fr"], "source_locale": "fr" }"#)
+      .unwrap();
+    let config = Config::new(dir.path(), false).unwrap();
+    assert_eq!(config.get_source_locale().map(String::as_str), Some("fr"));
+
+    std::fs::write(dir.path().join("i18next-parser.json"), r#"{ "locales": ["en", "fr"], "source_locale": "de" }"#)
+      .unwrap();
+    assert!(Config::new(dir.path(), false).is_err());
+  }
+
+  #[test_log::test]
+  fn config_new_reads_namespace_formats() {
+    let dir = tempdir::TempDir::new("config").unwrap();
+    std::fs::write(dir.path().join("i18next-parser.json"), r#"{ "namespace_formats": { "emails": "yaml" } }"#).unwrap();
+
+    let config = Config::new(dir.path(), false).unwrap();
+    assert_eq!(config.namespace_formats, HashMap::from([("emails".to_string(), OutputFormat::Yaml)]));
+  }
+
+  #[test_log::test]
+  fn config_init_refuses_to_overwrite() {
+    let dir = tempdir::TempDir::new("config").unwrap();
+    std::fs::write(dir.path().join("i18next-parser.yaml"), "locales: [fr]").unwrap();
+
+    assert!(Config::init(dir.path(), ConfigFileFormat::Json).is_err());
+    assert!(!dir.path().join(".i18next-parser.json").exists());
   }
 
   #[test_log::test]
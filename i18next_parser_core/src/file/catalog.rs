@@ -1,24 +1,33 @@
 //! This module provides functionality for reading files into serde values
-use std::{fs::File, io::BufReader, path::PathBuf};
+use std::path::PathBuf;
 
 use color_eyre::owo_colors::OwoColorize;
 use log::{trace, warn};
 use serde_json::Value;
 
-/// Read a file into a serde value
+/// The UTF-8 byte order mark, written at the start of the catalogs when `byte_order_mark` is set
+pub(crate) const BYTE_ORDER_MARK: &str = "\u{feff}";
+
+/// Read a file into a serde value, ignoring its byte order mark
 pub fn read_file_into_serde(path: &PathBuf) -> Option<Value> {
   trace!("Reading file: {}", path.display().yellow());
-  let file = File::open(path);
-  if file.is_err() && path.file_name().and_then(|f| f.to_str()).is_some_and(|name| !name.to_string().contains("_old")) {
+  let content = std::fs::read_to_string(path);
+  if content.is_err()
+    && path.file_name().and_then(|f| f.to_str()).is_some_and(|name| !name.to_string().contains("_old"))
+  {
     warn!("Unable to find file: {}", path.display().yellow());
   }
-  file.map_or(Default::default(), |file| {
-    let reader = BufReader::new(file);
-    if path.extension().is_some_and(|ext| ext == "yml") {
-      serde_yaml_ng::from_reader(reader).ok()
+  content.map_or(Default::default(), |content| {
+    let content = content.strip_prefix(BYTE_ORDER_MARK).unwrap_or(&content);
+    if path.extension().is_some_and(|ext| ext == "yml" || ext == "yaml") {
+      serde_yaml_ng::from_str(content).ok()
+    } else if path.extension().is_some_and(|ext| ext == "json5") {
+      // skip the header comment of the file
+      let content = content.lines().skip_while(|line| line.trim_start().starts_with("//")).collect::<Vec<_>>();
+      serde_json::from_str(&content.join("\n")).ok()
     } else {
       // read json file
-      serde_json::from_reader(reader).ok()
+      serde_json::from_str(content).ok()
     }
   })
 }
@@ -72,6 +81,16 @@ key4: value4
     assert_eq!(catalog_value["key4"], "value4");
   }
 
+  #[test_log::test]
+  fn test_get_catalog_with_byte_order_mark() {
+    let dir = TempDir::new("test").unwrap();
+    let path = dir.path().join("default.json");
+    std::fs::write(&path, format!("{BYTE_ORDER_MARK}{{\"key1\": \"value1\"}}")).unwrap();
+
+    let catalog = read_file_into_serde(&path);
+    assert_eq!(catalog.unwrap()["key1"], "value1");
+  }
+
   #[test_log::test]
   fn test_get_catalog_with_non_existing_file() {
     let path = PathBuf::from(BASE_PATH.to_owned() + "en/non_existing.json");
@@ -1,2 +1,3 @@
 pub mod parse_directory;
 pub mod parse_file;
+pub mod parse_mdx;
@@ -1,20 +1,24 @@
-use std::{num::NonZero, path::PathBuf, time::Instant};
+use std::{
+  collections::HashSet,
+  num::NonZero,
+  path::{Path, PathBuf},
+  time::Instant,
+};
 
 use color_eyre::{
   eyre::{bail, eyre},
   owo_colors::{CssColors, OwoColorize},
 };
 use ignore::DirEntry;
-use log::{debug, info};
+use log::{debug, info, warn};
 use tracing::instrument;
 
 use crate::{config::Config, file::parser::parse_file::parse_file, log_time, Entry};
 
-fn parse_directory_mono_thread<C: AsRef<Config>>(filter: &[DirEntry], config: C) -> Vec<Entry> {
+fn parse_directory_mono_thread<C: AsRef<Config>>(filter: &[PathBuf], config: C) -> Vec<Entry> {
   filter
     .iter()
-    .filter_map(move |entry| {
-      let entry_path = entry.path();
+    .filter_map(move |entry_path| {
       let now = Instant::now();
       let ret = parse_file(entry_path, &config).ok();
       let elapsed = now.elapsed().as_secs_f64() * 1000.0;
@@ -33,7 +37,7 @@ fn parse_directory_mono_thread<C: AsRef<Config>>(filter: &[DirEntry], config: C)
     .collect()
 }
 
-fn parse_directory_thread<'a>(parallelism: NonZero<usize>, filter: &'a [DirEntry], config: &'a Config) -> Vec<Entry> {
+fn parse_directory_thread<'a>(parallelism: NonZero<usize>, filter: &'a [PathBuf], config: &'a Config) -> Vec<Entry> {
   let len = filter.len();
   let items_per_threads = len / parallelism;
   let chunk_size = len.div_ceil(items_per_threads); // ceil(len / n)
@@ -55,9 +59,69 @@ fn parse_directory_thread<'a>(parallelism: NonZero<usize>, filter: &'a [DirEntry
     .collect::<Vec<_>>()
 }
 
-/// Parse a directory and return a list of entries.
+/// Create the glob matching the files of an input of the configuration, relative to the path.
+fn input_glob(path: &Path, input: &str) -> color_eyre::Result<globset::Glob> {
+  let join = path.join(input);
+  let glob = join.to_str().ok_or(eyre!("Invalid input glob {input:?}"))?;
+  Ok(globset::Glob::new(glob)?)
+}
+
+/// The files matched by an input glob of the configuration.
+#[derive(Debug, Default, PartialEq)]
+pub struct GlobExplanation {
+  /// the input glob, as configured
+  pub glob: String,
+  /// the files matched by the glob that are parsed
+  pub matched: Vec<PathBuf>,
+  /// the files matched by the glob that are skipped by the ignore files or as hidden files
+  pub ignored: Vec<PathBuf>,
+}
+
+/// Explain which files of a directory are matched and ignored by each input glob of the configuration.
+pub fn explain_globs<P: Into<PathBuf>, C: AsRef<Config>>(
+  path: P,
+  config: C,
+) -> color_eyre::Result<Vec<GlobExplanation>> {
+  let path = &path.into();
+  let config = config.as_ref();
+  if !path.exists() {
+    bail!("Directory {path:?} does not exist");
+  }
+
+  let walk = |standard_filters: bool| {
+    let mut files = ignore::WalkBuilder::new(path)
+      .standard_filters(standard_filters)
+      .build()
+      .filter_map(Result::ok)
+      .filter(|f| f.file_type().is_some_and(|t| t.is_file()))
+      .map(|f| f.into_path())
+      .collect::<Vec<_>>();
+    files.sort();
+    files
+  };
+  let parsed = walk(true).into_iter().collect::<HashSet<_>>();
+  let files = walk(false);
+
+  config
+    .input
+    .iter()
+    .map(|input| {
+      let glob = input_glob(path, input)?.compile_matcher();
+      let (matched, ignored) =
+        files.iter().filter(|file| glob.is_match(file)).cloned().partition(|file| parsed.contains(file));
+      Ok(GlobExplanation { glob: input.clone(), matched, ignored })
+    })
+    .collect()
+}
+
+/// Find the files of a directory matched by the input globs of the configuration.
+///
+/// # Errors
+///
+/// Returns an error when the directory does not exist, or when no file is matched and `config.error_on_no_input` is
+/// set.
 #[instrument(skip_all, err, target = "instrument")]
-pub fn parse_directory<P: Into<PathBuf>, C: AsRef<Config>>(path: P, config: C) -> color_eyre::Result<Vec<Entry>> {
+pub fn find_input_files<P: Into<PathBuf>, C: AsRef<Config>>(path: P, config: C) -> color_eyre::Result<Vec<PathBuf>> {
   let path = &path.into();
   let config = config.as_ref();
   debug!("Creating globset from {:?}", &config.input);
@@ -65,9 +129,7 @@ pub fn parse_directory<P: Into<PathBuf>, C: AsRef<Config>>(path: P, config: C) -
   let glob = {
     let mut builder = globset::GlobSetBuilder::new();
     for input in &config.input {
-      let join = path.join(input);
-      let glob = join.to_str().unwrap();
-      builder.add(globset::Glob::new(glob)?);
+      builder.add(input_glob(path, input)?);
     }
     builder.build()?
   };
@@ -85,31 +147,90 @@ pub fn parse_directory<P: Into<PathBuf>, C: AsRef<Config>>(path: P, config: C) -
 
   let directory_name =
     path.file_name().and_then(|s| s.to_str()).ok_or(eyre!("Unable to get filename of path {path:?}"))?;
-  log_time!(format!("Reading directory {}", directory_name.yellow()), {
-    debug!("Reading directory {} to find {:?}", path.display().yellow(), &config.input);
-    let filter = ignore::WalkBuilder::new(path)
-      .standard_filters(true)
-      .build()
-      .filter_map(Result::ok)
-      .filter(|f| glob.is_match(f.path()))
-      .collect::<Vec<_>>();
+  let files = ignore::WalkBuilder::new(path)
+    .standard_filters(true)
+    .build()
+    .filter_map(Result::ok)
+    .filter(|f| glob.is_match(f.path()))
+    .map(DirEntry::into_path)
+    .collect::<Vec<_>>();
 
-    debug!("Found {} entries", filter.len().blue());
-    if !filter.is_empty() {
-      let parallelism = std::thread::available_parallelism().unwrap();
-      let len = filter.len();
-
-      info!("Reading {} files", len.blue());
-      let entries = if len > parallelism.get() {
-        debug!("Using {parallelism} threads to read the directory {directory_name}");
-        parse_directory_thread(parallelism, &filter, config)
-      } else {
-        parse_directory_mono_thread(&filter, config)
-      };
-
-      Ok(entries)
-    } else {
-      bail!("No entries found in the directory {directory_name}")
+  debug!("Found {} entries", files.len().blue());
+  if files.is_empty() {
+    if config.error_on_no_input {
+      bail!("No entries found in the directory {directory_name}");
     }
+    warn!("No entries found in the directory {directory_name}");
+  }
+  Ok(files)
+}
+
+/// Parse the files and return a list of entries.
+pub fn parse_files<C: AsRef<Config>>(files: &[PathBuf], config: C) -> Vec<Entry> {
+  let config = config.as_ref();
+  let parallelism = std::thread::available_parallelism().unwrap();
+  let len = files.len();
+
+  info!("Reading {} files", len.blue());
+  if len > parallelism.get() {
+    debug!("Using {parallelism} threads to read the files");
+    parse_directory_thread(parallelism, files, config)
+  } else {
+    parse_directory_mono_thread(files, config)
+  }
+}
+
+/// Parse a directory and return a list of entries.
+#[instrument(skip_all, err, target = "instrument")]
+pub fn parse_directory<P: Into<PathBuf>, C: AsRef<Config>>(path: P, config: C) -> color_eyre::Result<Vec<Entry>> {
+  let path = &path.into();
+  let config = config.as_ref();
+  let directory_name =
+    path.file_name().and_then(|s| s.to_str()).ok_or(eyre!("Unable to get filename of path {path:?}"))?;
+  log_time!(format!("Reading directory {}", directory_name.yellow()), {
+    let files = find_input_files(path.clone(), config)?;
+    Ok(parse_files(&files, config))
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use tempdir::TempDir;
+
+  use super::*;
+
+  #[test_log::test]
+  fn should_explain_globs() {
+    let dir = TempDir::new("explain_globs").unwrap();
+    let src = dir.path().join("src");
+    fs::create_dir_all(&src).unwrap();
+    fs::write(src.join("app.ts"), "t('key')").unwrap();
+    fs::write(src.join("style.css"), "").unwrap();
+    fs::write(src.join(".hidden.ts"), "t('key')").unwrap();
+    let config = Config { input: vec!["src/**/*.ts".into(), "lib/**/*.ts".into()], ..Default::default() };
+
+    let explanations = explain_globs(dir.path(), &config).unwrap();
+
+    assert_eq!(explanations, vec![
+      GlobExplanation {
+        glob: "src/**/*.ts".into(),
+        matched: vec![src.join("app.ts")],
+        ignored: vec![src.join(".hidden.ts")],
+      },
+      GlobExplanation { glob: "lib/**/*.ts".into(), ..Default::default() },
+    ]);
+  }
+
+  #[test_log::test]
+  fn should_fail_without_input_files_when_error_on_no_input_is_set() {
+    let dir = TempDir::new("no_input").unwrap();
+    fs::write(dir.path().join("style.css"), "").unwrap();
+
+    assert!(parse_directory(dir.path(), Config::default()).is_err());
+
+    let config = Config { error_on_no_input: false, ..Default::default() };
+    assert_eq!(parse_directory(dir.path(), config).unwrap(), vec![]);
+  }
+}
@@ -1,4 +1,4 @@
-use std::{fs::read_to_string, path::Path};
+use std::{borrow::Cow, fs::read_to_string, path::Path};
 
 use color_eyre::owo_colors::OwoColorize;
 use log::trace;
@@ -7,18 +7,35 @@ use oxc_ast::Visit;
 use oxc_parser::Parser;
 use oxc_span::SourceType;
 
-use crate::{log_time, visitor::I18NVisitor, Config, Entry};
+use crate::{
+  file::parser::parse_mdx::mdx_to_jsx,
+  helper::line_index::LineIndex,
+  log_time,
+  visitor::I18NVisitor,
+  Config,
+  Entry,
+};
 
 pub fn parse_file<P: AsRef<Path>, C: AsRef<Config>>(path: P, config: C) -> color_eyre::Result<Vec<Entry>> {
   let path = path.as_ref();
   let file_name = path.file_name().and_then(|s| s.to_str()).unwrap();
-  let source_text = log_time!(format!("Reading file {}", file_name.yellow().italic()), { read_to_string(path) })?;
+  let file_text = log_time!(format!("Reading file {}", file_name.yellow().italic()), { read_to_string(path) })?;
+  let is_mdx = path.extension().is_some_and(|extension| extension == "mdx");
+  let (source_text, line_index) = if is_mdx {
+    let (jsx, chunks) = mdx_to_jsx(&file_text);
+    (Cow::Owned(jsx), LineIndex::with_chunks(&file_text, chunks))
+  } else {
+    (Cow::Borrowed(file_text.as_str()), LineIndex::new(&file_text))
+  };
 
   let allocator = &Allocator::default();
-  let source_type = SourceType::from_path(path).unwrap();
-  let parser = Parser::new(allocator, source_text.as_str(), source_type);
+  let source_type =
+    if is_mdx { SourceType::default().with_module(true).with_jsx(true) } else { SourceType::from_path(path).unwrap() };
+  let parser = Parser::new(allocator, &source_text, source_type);
   let parsed = parser.parse();
-  let mut visitor = I18NVisitor::new(&parsed.program, path, config);
+  let mut visitor = I18NVisitor::new(&parsed.program, path, config)
+    .with_comments(&source_text, &parsed.trivias)
+    .with_line_index(line_index);
 
   trace!("Start parsing file {}...", file_name.yellow().italic());
   log_time!(format!("Parsing file {}", file_name.yellow()), {
@@ -28,3 +45,91 @@ pub fn parse_file<P: AsRef<Path>, C: AsRef<Config>>(path: P, config: C) -> color
 
   Ok(visitor.entries)
 }
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use tempdir::TempDir;
+
+  use super::*;
+
+  #[test_log::test]
+  fn should_parse_mdx_file() {
+    let dir = TempDir::new("parse_file").unwrap();
+    let path = dir.path().join("page.mdx");
+    std::fs::write(&path, "# {t('page.title', 'Title')}\n\n<Trans i18nKey='page.body'>Body</Trans>\n").unwrap();
+
+    let entries = parse_file(&path, Config::default()).unwrap();
+
+    assert_eq!(entries, vec![Entry::new_with_value("page.title", "Title"), Entry::new_with_value("page.body", "Body")]);
+  }
+
+  #[test_log::test]
+  fn should_report_entries_location() {
+    let dir = TempDir::new("parse_file").unwrap();
+    let path = dir.path().join("page.tsx");
+    std::fs::write(
+      &path,
+      "const title = t('title', 'Title');\nconst body = <p>\n  <Trans i18nKey='body'>Body</Trans>\n</p>;\n",
+    )
+    .unwrap();
+
+    let entries = parse_file(&path, Config::default()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let locations = entries.iter().map(|entry| entry.location.as_ref().unwrap().to_string()).collect::<Vec<_>>();
+
+    assert_eq!(locations, vec![format!("{}:1:15", path.display()), format!("{}:3:3", path.display())]);
+  }
+
+  #[test_log::test]
+  fn should_report_entries_location_in_mdx_file() {
+    let dir = TempDir::new("parse_file").unwrap();
+    let path = dir.path().join("page.mdx");
+    std::fs::write(&path, "# Titré\n\n<Trans i18nKey='body'>Body</Trans>\n\nSome text {t('after')}\n").unwrap();
+
+    let entries = parse_file(&path, Config::default()).unwrap();
+    let locations = entries.iter().map(|entry| entry.location.as_ref().unwrap().to_string()).collect::<Vec<_>>();
+
+    assert_eq!(locations, vec![format!("{}:3:1", path.display()), format!("{}:5:12", path.display())]);
+  }
+
+  #[test_log::test]
+  fn should_resolve_the_namespace_imported_from_another_module() {
+    let dir = TempDir::new("parse_file").unwrap();
+    std::fs::create_dir_all(dir.path().join("constants")).unwrap();
+    std::fs::write(dir.path().join("constants/ns.ts"), "export const NS = 'common';\n").unwrap();
+    let path = dir.path().join("page.tsx");
+    std::fs::write(
+      &path,
+      "import { NS as namespace } from './constants/ns';\nconst { t } = useTranslation(namespace);\nt('title');\n",
+    )
+    .unwrap();
+
+    let entries = parse_file(&path, Config { resolve_imports: true, ..Default::default() }).unwrap();
+    assert_eq!(entries, vec![Entry::new_with_ns("title", "common")]);
+
+    let entries = parse_file(&path, Config::default()).unwrap();
+    assert_eq!(entries, vec![Entry::empty("title")]);
+  }
+
+  #[test_log::test]
+  fn should_resolve_the_namespace_imported_from_the_input_roots() {
+    let dir = TempDir::new("parse_file").unwrap();
+    std::fs::create_dir_all(dir.path().join("src/constants")).unwrap();
+    std::fs::create_dir_all(dir.path().join("src/pages")).unwrap();
+    std::fs::write(dir.path().join("src/constants/index.ts"), "export const NS = 'common';\nconst OTHER = 1;\n")
+      .unwrap();
+    let path = dir.path().join("src/pages/page.tsx");
+    std::fs::write(&path, "import { NS } from 'constants';\nconst { t } = useTranslation(NS);\nt('title');\n").unwrap();
+    let config = Config {
+      working_dir: dir.path().to_path_buf(),
+      input: vec!["src/**/*.tsx".into()],
+      resolve_imports: true,
+      ..Default::default()
+    };
+
+    assert_eq!(parse_file(&path, &config).unwrap(), vec![Entry::new_with_ns("title", "common")]);
+    // parsing again reads the cached constants of the module
+    assert_eq!(parse_file(&path, &config).unwrap(), vec![Entry::new_with_ns("title", "common")]);
+  }
+}
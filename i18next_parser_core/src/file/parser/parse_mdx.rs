@@ -0,0 +1,234 @@
+//! This module extracts the javascript parts of a MDX file so that they can be parsed as a jsx file.
+
+/// The name of the translation component extracted from MDX files.
+const TRANS_TAG: &str = "Trans";
+
+/// Find the end of the opening tag starting at `start`, skipping the attribute expressions and strings.
+///
+/// # Returns
+///
+/// The index following the `>` of the tag, and whether the tag is self-closing.
+fn find_tag_end(bytes: &[u8], start: usize) -> Option<(usize, bool)> {
+  let mut depth = 0;
+  let mut quote = None;
+  let mut idx = start;
+  while idx < bytes.len() {
+    let byte = bytes[idx];
+    match (quote, byte) {
+      (Some(q), b) if b == q => quote = None,
+      (Some(_), _) => {},
+      (None, b'"' | b'\'' | b'`') => quote = Some(byte),
+      (None, b'{') => depth += 1,
+      (None, b'}') => depth -= 1,
+      (None, b'>') if depth == 0 => return Some((idx + 1, bytes[idx - 1] == b'/')),
+      _ => {},
+    }
+    idx += 1;
+  }
+  None
+}
+
+/// Find the end of the expression whose opening brace is at `start`.
+///
+/// # Returns
+///
+/// The index of the matching closing brace.
+fn find_expression_end(bytes: &[u8], start: usize) -> Option<usize> {
+  let mut depth = 0;
+  let mut quote = None;
+  for (idx, byte) in bytes.iter().enumerate().skip(start) {
+    match (quote, *byte) {
+      (Some(q), b) if b == q => quote = None,
+      (Some(_), _) => {},
+      (None, b'"' | b'\'' | b'`') => quote = Some(*byte),
+      (None, b'{') => depth += 1,
+      (None, b'}') => {
+        depth -= 1;
+        if depth == 0 {
+          return Some(idx);
+        }
+      },
+      _ => {},
+    }
+  }
+  None
+}
+
+/// Check if a `Trans` opening tag starts at `idx`.
+///
+/// The bytes are compared instead of the text, as `idx` may not be on a char boundary.
+fn is_trans_tag(bytes: &[u8], idx: usize) -> bool {
+  let tag = format!("<{TRANS_TAG}");
+  bytes[idx..].starts_with(tag.as_bytes())
+    && bytes.get(idx + tag.len()).is_some_and(|byte| byte.is_ascii_whitespace() || matches!(byte, b'>' | b'/'))
+}
+
+/// Find the end of the `Trans` element starting at `start`, including its nested `Trans` elements.
+///
+/// # Returns
+///
+/// The index following the end of the element.
+fn find_element_end(bytes: &[u8], start: usize) -> Option<usize> {
+  let closing = format!("</{TRANS_TAG}>");
+  let (mut idx, self_closing) = find_tag_end(bytes, start)?;
+  if self_closing {
+    return Some(idx);
+  }
+
+  let mut depth = 1;
+  while idx < bytes.len() {
+    if bytes[idx..].starts_with(closing.as_bytes()) {
+      depth -= 1;
+      idx += closing.len();
+      if depth == 0 {
+        return Some(idx);
+      }
+    } else if is_trans_tag(bytes, idx) {
+      let (end, self_closing) = find_tag_end(bytes, idx)?;
+      if !self_closing {
+        depth += 1;
+      }
+      idx = end;
+    } else {
+      idx += 1;
+    }
+  }
+  None
+}
+
+/// Convert a MDX source to a jsx source containing only its javascript parts.
+///
+/// The `import`/`export` statements, the `{...}` expressions and the `Trans` elements found outside of the
+/// code blocks and inline code are kept, each as its own statement. Everything else is considered as markdown
+/// and dropped.
+///
+/// # Arguments
+///
+/// * `source` - The MDX source to convert.
+///
+/// # Returns
+///
+/// The jsx source extracted from the MDX, and the offset of each kept part in the jsx source and in the MDX source.
+pub fn mdx_to_jsx(source: &str) -> (String, Vec<(u32, usize)>) {
+  let mut output = String::new();
+  let mut chunks = vec![];
+  let mut push_chunk = |output: &mut String, content: &str, offset: usize, wrap: bool| {
+    if wrap {
+      output.push('(');
+    }
+    chunks.push((output.len() as u32, offset));
+    output.push_str(content);
+    output.push_str(if wrap { ");\n" } else { "\n" });
+  };
+
+  // the markdown is kept as is, the other lines are blanked so that the offsets of the text match the source
+  let blank = |line: &str| line.bytes().map(|byte| if byte == b'\n' { '\n' } else { ' ' }).collect::<String>();
+  let mut text = String::new();
+  let mut in_code_block = false;
+  for line in source.split_inclusive('\n') {
+    let trimmed = line.trim_start();
+    if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+      in_code_block = !in_code_block;
+      text.push_str(&blank(line));
+    } else if in_code_block {
+      text.push_str(&blank(line));
+    } else if trimmed.starts_with("import ") || trimmed.starts_with("export ") {
+      push_chunk(&mut output, line.trim_end_matches(['\r', '\n']), text.len(), false);
+      text.push_str(&blank(line));
+    } else {
+      text.push_str(line);
+    }
+  }
+
+  let bytes = text.as_bytes();
+  let mut idx = 0;
+  while idx < bytes.len() {
+    match bytes[idx] {
+      b'`' => {
+        // inline code is kept as markdown
+        idx = text[idx + 1..].find('`').map_or(bytes.len(), |end| idx + end + 2);
+      },
+      b'{' => {
+        match find_expression_end(bytes, idx) {
+          Some(end) => {
+            push_chunk(&mut output, &text[idx + 1..end], idx + 1, true);
+            idx = end + 1;
+          },
+          None => idx += 1,
+        }
+      },
+      b'<' if is_trans_tag(bytes, idx) => {
+        match find_element_end(bytes, idx) {
+          Some(end) => {
+            push_chunk(&mut output, &text[idx..end], idx, true);
+            idx = end;
+          },
+          None => idx += 1,
+        }
+      },
+      _ => idx += 1,
+    }
+  }
+
+  (output, chunks)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn extracts_expressions_and_trans_elements() {
+    let source = r#"import { Trans } from "react-i18next";
+
+# {t("title")}
+
+Some text with `{t("inline.code")}` and a component:
+
+<Trans i18nKey="description">
+  Hello <strong>{{ name }}</strong>
+</Trans>
+
+<Trans i18nKey="self" />
+"#;
+    let expected = r#"import { Trans } from "react-i18next";
+(t("title"));
+(<Trans i18nKey="description">
+  Hello <strong>{{ name }}</strong>
+</Trans>);
+(<Trans i18nKey="self" />);
+"#;
+    assert_eq!(mdx_to_jsx(source).0, expected);
+  }
+
+  #[test]
+  fn ignores_code_blocks() {
+    let source = "```js\nconst title = t('code.block');\n```\n{t('outside')}\n";
+    assert_eq!(mdx_to_jsx(source).0, "(t('outside'));\n");
+  }
+
+  #[test]
+  fn keeps_nested_trans_elements() {
+    let source = "<Trans i18nKey='outer'>Hello <Trans i18nKey='inner'>world</Trans></Trans> {t('after')}";
+    assert_eq!(
+      mdx_to_jsx(source).0,
+      "(<Trans i18nKey='outer'>Hello <Trans i18nKey='inner'>world</Trans></Trans>);\n(t('after'));\n"
+    );
+  }
+
+  #[test]
+  fn keeps_non_ascii_trans_content() {
+    let source = "# Titre\n\n<Trans i18nKey='x'>Héllo wörld</Trans>\n";
+    assert_eq!(mdx_to_jsx(source).0, "(<Trans i18nKey='x'>Héllo wörld</Trans>);\n");
+  }
+
+  #[test]
+  fn maps_the_kept_parts_to_the_source() {
+    let source = "import a from 'a';\n\n# {t('title')}\n<Trans>Hi</Trans>\n";
+    let (jsx, chunks) = mdx_to_jsx(source);
+    assert_eq!(jsx, "import a from 'a';\n(t('title'));\n(<Trans>Hi</Trans>);\n");
+    assert_eq!(chunks, vec![(0, 0), (20, 23), (34, 35)]);
+  }
+}
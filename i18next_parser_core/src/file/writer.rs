@@ -1,18 +1,24 @@
 //! This module provides functionality for writing files
 use std::{
+  cmp::Ordering,
   fs::File,
   io::Write,
-  path::{Path, PathBuf},
+  path::{Component, Path, PathBuf},
 };
 
-use color_eyre::{owo_colors::OwoColorize, Report};
+use color_eyre::{eyre::bail, owo_colors::OwoColorize, Report};
 use log::{debug, trace};
+use serde::{ser::SerializeMap, Serialize, Serializer};
 use serde_json::Value;
 use tracing::instrument;
 
 use crate::{
-  config::{Config, LineEnding},
-  helper::merge_hashes::MergeResult,
+  config::{Config, LineEnding, SortOrder},
+  file::catalog::BYTE_ORDER_MARK,
+  helper::{
+    merge_hashes::{get_variant_base, MergeResult},
+    natural_cmp::natural_cmp,
+  },
   is_empty::IsEmpty,
   log_time,
   merger::merge_results::MergeResults,
@@ -24,7 +30,9 @@ pub fn write_to_file<T: AsRef<Config>>(values: &[MergeResults], config: T) -> co
   let config = config.as_ref();
   log_time!("Writing files", {
     for value in values {
-      let MergeResults { namespace: _namespace, locale: _locale, path, backup, merged, old_catalog } = value;
+      let MergeResults { path, backup, merged, old_catalog, .. } = value;
+      ensure_within_working_dir(path, config)?;
+      ensure_within_working_dir(backup, config)?;
       write_files(path, backup, merged, old_catalog, config)?;
     }
 
@@ -32,25 +40,79 @@ pub fn write_to_file<T: AsRef<Config>>(values: &[MergeResults], config: T) -> co
   })
 }
 
+/// Make the path absolute and resolve its `.` and `..` components, without following the symbolic links
+fn normalize_path(path: &Path) -> std::io::Result<PathBuf> {
+  let path = std::path::absolute(path)?;
+  Ok(path.components().fold(PathBuf::new(), |mut normalized, component| {
+    match component {
+      Component::ParentDir => {
+        normalized.pop();
+      },
+      Component::CurDir => {},
+      component => normalized.push(component),
+    }
+    normalized
+  }))
+}
+
+/// Check that the path is inside the working directory, unless `config.allow_outside_output` is set
+fn ensure_within_working_dir(path: &Path, config: &Config) -> color_eyre::Result<()> {
+  if config.allow_outside_output {
+    return Ok(());
+  }
+
+  let working_dir = normalize_path(&config.working_dir)?;
+  if !normalize_path(path)?.starts_with(&working_dir) {
+    bail!(
+      "The output {} resolves outside of the working directory {}, set allow_outside_output to allow it",
+      path.display().yellow(),
+      working_dir.display().yellow()
+    );
+  }
+  Ok(())
+}
+
 fn write_files<T: AsRef<Config>>(
-  path: &PathBuf,
-  backup: &PathBuf,
+  path: &Path,
+  backup: &Path,
   merged: &MergeResult,
   old_catalog: &Value,
   config: T,
 ) -> Result<(), Report> {
   let config = config.as_ref();
   log_time!(format!("Writing file {:?}", path.yellow()), {
-    let new_catalog = &merged.new;
-    push_file(path, new_catalog, config)?;
-    if config.create_old_catalogs && !old_catalog.is_empty() {
+    // the old catalog is written first, so that the catalog is left as is when its removed keys cannot be archived
+    if (config.create_old_catalogs || config.keep_backup_on_clean) && !old_catalog.is_empty() {
       push_file(backup, old_catalog, config)?;
     }
+    push_file(path, &merged.new, config)?;
     Ok(())
   })
 }
 
-fn push_file<T: AsRef<Config>>(path: &PathBuf, contents: &Value, config: T) -> std::io::Result<()> {
+/// Serializes a value with the keys of its objects sorted by the comparison function
+struct SortedKeys<'a, F>(&'a Value, &'a F);
+
+impl<F: Fn(&str, &str) -> Ordering> Serialize for SortedKeys<'_, F> {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let SortedKeys(value, cmp) = self;
+    match value {
+      Value::Object(map) => {
+        let mut entries = map.iter().collect::<Vec<_>>();
+        entries.sort_by(|(a, _), (b, _)| cmp(a, b));
+        let mut map = serializer.serialize_map(Some(entries.len()))?;
+        for (key, value) in entries {
+          map.serialize_entry(key, &SortedKeys(value, *cmp))?;
+        }
+        map.end()
+      },
+      Value::Array(values) => serializer.collect_seq(values.iter().map(|value| SortedKeys(value, *cmp))),
+      value => value.serialize(serializer),
+    }
+  }
+}
+
+fn push_file<T: AsRef<Config>>(path: &Path, contents: &Value, config: T) -> std::io::Result<()> {
   fn handle_line_ending(text: &str, line_ending: &LineEnding) -> String {
     match line_ending {
       LineEnding::Crlf => text.replace('\n', "\r\n"),
@@ -62,27 +124,357 @@ fn push_file<T: AsRef<Config>>(path: &PathBuf, contents: &Value, config: T) -> s
     }
   }
 
-  let text = {
-    let text = if path.ends_with("yml") {
+  fn to_text<V: Serialize>(path: &Path, contents: &V) -> String {
+    if path.extension().is_some_and(|ext| ext == "yml" || ext == "yaml") {
       serde_yaml_ng::to_string(contents).unwrap()
     } else {
       serde_json::to_string_pretty(contents).map(|t| t.replace("\r\n", "\n").replace('\r', "\n")).unwrap()
+    }
+  }
+
+  let config = config.as_ref();
+  let text = {
+    let cmp_keys = |a: &str, b: &str| {
+      match config.sort_order {
+        SortOrder::Lexicographic => a.cmp(b),
+        SortOrder::Natural => natural_cmp(a, b),
+      }
+    };
+    // the variants are sorted after their base key, so that they are written next to it
+    let cmp_variants = |a: &str, b: &str| {
+      cmp_keys(&get_variant_base(a, config), &get_variant_base(b, config)).then_with(|| cmp_keys(a, b))
+    };
+    // serde_json already sorts the keys of the objects lexicographically
+    let text = match (&config.sort_order, config.group_variants) {
+      _ if !config.sort => to_text(path, contents),
+      (_, true) => to_text(path, &SortedKeys(contents, &cmp_variants)),
+      (SortOrder::Natural, false) => to_text(path, &SortedKeys(contents, &cmp_keys)),
+      (SortOrder::Lexicographic, false) => to_text(path, contents),
+    };
+
+    // only the JSON5 and YAML catalogs can hold comments
+    let comment_marker = match path.extension().and_then(|ext| ext.to_str()) {
+      Some("json5") => Some("//"),
+      Some("yml" | "yaml") => Some("#"),
+      _ => None,
+    };
+    let text = match (&config.file_header, comment_marker) {
+      (Some(header), Some(marker)) => {
+        let header = header.lines().map(|line| format!("{marker} {line}\n")).collect::<String>();
+        format!("{header}{text}")
+      },
+      _ => text,
     };
 
-    handle_line_ending(&text, &config.as_ref().line_ending)
+    let text = handle_line_ending(&text, &config.line_ending);
+    if config.byte_order_mark {
+      format!("{BYTE_ORDER_MARK}{text}")
+    } else {
+      text
+    }
   };
 
   if let Some(parent) = path.parent() {
     if !parent.exists() {
+      if !config.create_dirs {
+        return Err(std::io::Error::new(
+          std::io::ErrorKind::NotFound,
+          format!("The directory {} does not exist, set create_dirs to create it", parent.display()),
+        ));
+      }
       trace!("creating parent directory: {:?}", parent);
       std::fs::create_dir_all(parent)?;
     }
   }
   trace!("Writing {} to {}", contents.cyan(), path.display().yellow());
-  let mut file = File::create(Path::new(path))?;
   let bytes = text.as_bytes();
-  file.write_all(bytes)?;
+  write_atomically(path, bytes)?;
   debug!("Wrote {} bytes to {}", bytes.len().cyan(), path.display().yellow());
 
   Ok(())
 }
+
+/// Get the path of the temporary file used while writing `path`
+fn get_temp_path(path: &Path) -> PathBuf {
+  let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+  path.with_file_name(format!(".{file_name}.tmp"))
+}
+
+/// Write the bytes to a temporary file next to `path`, then rename it over `path`
+/// so that an interrupted run never leaves a partially written file
+fn write_atomically(path: &Path, bytes: &[u8]) -> std::io::Result<()> {
+  let temp_path = get_temp_path(path);
+  trace!("Writing temporary file {}", temp_path.display().yellow());
+  let result = File::create(&temp_path).and_then(|mut file| {
+    file.write_all(bytes)?;
+    file.sync_all()
+  });
+
+  match result.and_then(|_| std::fs::rename(&temp_path, path)) {
+    Ok(_) => Ok(()),
+    Err(error) => {
+      if temp_path.is_file() {
+        let _ = std::fs::remove_file(&temp_path);
+      }
+      Err(error)
+    },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+  use tempdir::TempDir;
+
+  use super::*;
+  use crate::{config::OutputFormat, merge_all_values, Entry};
+
+  #[test]
+  fn should_replace_the_file_content() {
+    let dir = TempDir::new("writer").unwrap();
+    let path = dir.path().join("en").join("translation.json");
+    let config = Config::default();
+
+    push_file(&path, &json!({ "key": "old" }), &config).unwrap();
+    push_file(&path, &json!({ "key": "new" }), &config).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content, serde_json::to_string_pretty(&json!({ "key": "new" })).unwrap());
+    assert!(!get_temp_path(&path).exists());
+  }
+
+  #[test]
+  fn should_create_the_missing_directories_only_when_create_dirs_is_set() {
+    let dir = TempDir::new("writer").unwrap();
+    let path = dir.path().join("en").join("translation.json");
+
+    let config = Config { create_dirs: false, ..Default::default() };
+    let error = push_file(&path, &json!({ "key": "value" }), &config).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+    assert!(!dir.path().join("en").exists());
+
+    push_file(&path, &json!({ "key": "value" }), Config::default()).unwrap();
+    assert!(path.exists());
+  }
+
+  #[test]
+  fn should_keep_the_old_content_when_the_write_fails() {
+    let dir = TempDir::new("writer").unwrap();
+    let path = dir.path().join("translation.json");
+    let config = Config::default();
+
+    push_file(&path, &json!({ "key": "old" }), &config).unwrap();
+    // a directory in place of the temporary file makes the write fail
+    std::fs::create_dir(get_temp_path(&path)).unwrap();
+
+    assert!(push_file(&path, &json!({ "key": "new" }), &config).is_err());
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content, serde_json::to_string_pretty(&json!({ "key": "old" })).unwrap());
+  }
+
+  #[test]
+  fn should_write_the_byte_order_mark() {
+    let dir = TempDir::new("writer").unwrap();
+    let path = dir.path().join("translation.json");
+
+    push_file(&path, &json!({ "key": "value" }), Config { byte_order_mark: true, ..Default::default() }).unwrap();
+    let content = std::fs::read(&path).unwrap();
+    assert!(content.starts_with(&[0xEF, 0xBB, 0xBF]));
+    assert_eq!(crate::file::catalog::read_file_into_serde(&path), Some(json!({ "key": "value" })));
+
+    push_file(&path, &json!({ "key": "value" }), Config::default()).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap()[0], b'{');
+  }
+
+  #[test]
+  fn should_write_the_keys_in_natural_order() {
+    let dir = TempDir::new("writer").unwrap();
+    let path = dir.path().join("translation.json");
+    let config = Config { sort_order: SortOrder::Natural, ..Default::default() };
+
+    push_file(&path, &json!({ "item10": "10", "item2": "2", "nested": { "b10": "", "b9": "" } }), &config).unwrap();
+
+    let content = std::fs::read_to_string(&path).unwrap();
+    let keys = content.lines().filter_map(|line| line.trim().split('"').nth(1)).collect::<Vec<_>>();
+    assert_eq!(keys, vec!["item2", "item10", "nested", "b9", "b10"]);
+  }
+
+  #[test]
+  fn should_write_the_variants_next_to_their_key() {
+    let dir = TempDir::new("writer").unwrap();
+    let path = dir.path().join("translation.json");
+    let contents =
+      json!({ "item": "", "item_other": "", "itemA": "", "item_one": "", "item-list": "", "item_male": "" });
+    let keys = |config: &Config| {
+      push_file(&path, &contents, config).unwrap();
+      let content = std::fs::read_to_string(&path).unwrap();
+      content.lines().filter_map(|line| line.trim().split('"').nth(1).map(String::from)).collect::<Vec<_>>()
+    };
+
+    assert_eq!(keys(&Config::default()), vec!["item", "item-list", "itemA", "item_male", "item_one", "item_other"]);
+
+    let config = Config { group_variants: true, ..Default::default() };
+    assert_eq!(keys(&config), vec!["item", "item_male", "item_one", "item_other", "item-list", "itemA"]);
+  }
+
+  #[test]
+  fn should_sort_the_object_keys_but_not_the_arrays() {
+    let dir = TempDir::new("writer").unwrap();
+    let path = dir.path().join("translation.json");
+    let contents = json!({ "list": ["item10", "item2", { "b": "", "a": "" }], "b": "", "a": "" });
+    let keys = |config: &Config| {
+      push_file(&path, &contents, config).unwrap();
+      let content = std::fs::read_to_string(&path).unwrap();
+      content.lines().filter_map(|line| line.trim().split('"').nth(1).map(String::from)).collect::<Vec<_>>()
+    };
+    let expected = vec!["a", "b", "list", "item10", "item2", "a", "b"];
+
+    assert_eq!(keys(&Config::default()), expected);
+    assert_eq!(keys(&Config { sort_order: SortOrder::Natural, ..Default::default() }), expected);
+    assert_eq!(keys(&Config { group_variants: true, ..Default::default() }), expected);
+  }
+
+  #[test]
+  fn should_write_the_file_header_in_the_json5_and_yaml_catalogs() {
+    let dir = TempDir::new("writer").unwrap();
+    let config = Config { file_header: Some("AUTO-GENERATED\nDo not edit".into()), ..Default::default() };
+    let content = |file: &str| {
+      let path = dir.path().join(file);
+      push_file(&path, &json!({ "key": "value" }), &config).unwrap();
+      assert_eq!(crate::file::catalog::read_file_into_serde(&path), Some(json!({ "key": "value" })));
+      std::fs::read_to_string(&path).unwrap()
+    };
+
+    assert!(content("translation.json5").starts_with("// AUTO-GENERATED\n// Do not edit\n{"));
+    assert!(content("translation.yml").starts_with("# AUTO-GENERATED\n# Do not edit\nkey: value"));
+    assert!(content("translation.json").starts_with('{'));
+  }
+
+  #[test]
+  fn should_write_each_namespace_in_its_format() {
+    let dir = TempDir::new("writer").unwrap();
+    let config = Config {
+      working_dir: dir.path().to_path_buf(),
+      output: "locales/$LOCALE/$NAMESPACE.json".into(),
+      namespace_formats: [("emails".to_string(), OutputFormat::Yaml)].into(),
+      ..Default::default()
+    };
+    let entries = vec![Entry::new("title", "Title", "translation"), Entry::new("subject", "Subject", "emails")];
+
+    let results = merge_all_values(entries, &config).unwrap();
+    write_to_file(&results, &config).unwrap();
+
+    let translation = std::fs::read_to_string(dir.path().join("locales/en/translation.json")).unwrap();
+    assert_eq!(translation, serde_json::to_string_pretty(&json!({ "title": "Title" })).unwrap());
+    let emails = std::fs::read_to_string(dir.path().join("locales/en/emails.yml")).unwrap();
+    assert_eq!(emails, "subject: Subject\n");
+    assert!(!dir.path().join("locales/en/emails.json").exists());
+  }
+
+  #[test]
+  fn should_archive_the_removed_keys_when_create_old_catalogs_is_set() {
+    let dir = TempDir::new("writer").unwrap();
+    let catalog = dir.path().join("locales/en/translation.json");
+    let backup = |config: &Config| {
+      push_file(&catalog, &json!({ "title": "Title", "removed": "Removed" }), config).unwrap();
+      let results = merge_all_values(vec![Entry::new("title", "Title", "translation")], config).unwrap();
+      write_to_file(&results, config).unwrap();
+      crate::file::catalog::read_file_into_serde(&results[0].backup)
+    };
+    let config = Config {
+      working_dir: dir.path().to_path_buf(),
+      output: "locales/$LOCALE/$NAMESPACE.json".into(),
+      ..Default::default()
+    };
+
+    assert_eq!(backup(&config), None);
+    let config = Config { create_old_catalogs: true, ..config };
+    assert_eq!(backup(&config), Some(json!({ "removed": "Removed" })));
+    assert_eq!(crate::file::catalog::read_file_into_serde(&catalog), Some(json!({ "title": "Title" })));
+  }
+
+  #[test]
+  fn should_archive_the_removed_keys_when_keep_backup_on_clean_is_set() {
+    let dir = TempDir::new("writer").unwrap();
+    let catalog = dir.path().join("locales/en/translation.json");
+    let backup = |config: &Config| {
+      push_file(&catalog, &json!({ "title": "Title", "removed": "Removed" }), config).unwrap();
+      let results = merge_all_values(vec![Entry::new("title", "Title", "translation")], config).unwrap();
+      write_to_file(&results, config).unwrap();
+      crate::file::catalog::read_file_into_serde(&results[0].backup)
+    };
+    let config = Config {
+      working_dir: dir.path().to_path_buf(),
+      output: "locales/$LOCALE/$NAMESPACE.json".into(),
+      keep_backup_on_clean: true,
+      ..Default::default()
+    };
+
+    // the removed keys are kept in the catalog, none is archived
+    assert_eq!(backup(&Config { keep_removed: true, ..config.clone() }), None);
+    assert_eq!(
+      crate::file::catalog::read_file_into_serde(&catalog),
+      Some(json!({ "title": "Title", "removed": "Removed" }))
+    );
+
+    assert!(!config.create_old_catalogs);
+    assert_eq!(backup(&config), Some(json!({ "removed": "Removed" })));
+    assert_eq!(crate::file::catalog::read_file_into_serde(&catalog), Some(json!({ "title": "Title" })));
+  }
+
+  #[test]
+  fn should_write_the_old_catalogs_under_the_backup_dir() {
+    let dir = TempDir::new("writer").unwrap();
+    let catalog = dir.path().join("locales/en/translation.json");
+    let backup = dir.path().join("backup/locales/en/translation_old.json");
+    let config = Config {
+      working_dir: dir.path().to_path_buf(),
+      output: "locales/$LOCALE/$NAMESPACE.json".into(),
+      backup_dir: Some("backup".into()),
+      create_old_catalogs: true,
+      ..Default::default()
+    };
+    let write = |config: &Config| {
+      let results = merge_all_values(vec![Entry::new("title", "Title", "translation")], config).unwrap();
+      write_to_file(&results, config)
+    };
+
+    // the catalog is left as is when the backup directory is missing and cannot be created
+    push_file(&catalog, &json!({ "title": "Title", "removed": "Removed" }), &config).unwrap();
+    let config_without_dirs = Config { create_dirs: false, ..config.clone() };
+    assert!(write(&config_without_dirs).is_err());
+    assert!(!backup.exists());
+    assert_eq!(
+      crate::file::catalog::read_file_into_serde(&catalog),
+      Some(json!({ "title": "Title", "removed": "Removed" }))
+    );
+
+    write(&config).unwrap();
+    assert_eq!(crate::file::catalog::read_file_into_serde(&backup), Some(json!({ "removed": "Removed" })));
+    assert_eq!(crate::file::catalog::read_file_into_serde(&catalog), Some(json!({ "title": "Title" })));
+  }
+
+  #[test]
+  fn should_reject_an_output_outside_of_the_working_dir() {
+    let dir = TempDir::new("writer").unwrap();
+    let working_dir = dir.path().join("project");
+    let result = |output: &str, allow_outside_output| {
+      let config =
+        Config { working_dir: working_dir.clone(), output: output.into(), allow_outside_output, ..Default::default() };
+      let path =
+        PathBuf::from(config.get_output().unwrap().replace("$LOCALE", "en").replace("$NAMESPACE", "translation"));
+      let results = [MergeResults { backup: path.clone(), path, ..Default::default() }];
+      write_to_file(&results, &config)
+    };
+
+    assert!(result("../etc/$NAMESPACE.json", false).is_err());
+    assert!(!dir.path().join("etc").exists());
+    assert!(result("locales/../../$LOCALE/$NAMESPACE.json", false).is_err());
+
+    assert!(result("locales/$LOCALE/$NAMESPACE.json", false).is_ok());
+    assert!(working_dir.join("locales/en/translation.json").exists());
+    assert!(result("../outside/$NAMESPACE.json", true).is_ok());
+    assert!(dir.path().join("outside/translation.json").exists());
+  }
+}
@@ -38,8 +38,7 @@ pub fn generate_types<C: AsRef<Config>>(entries: &[MergeResults], config: C) ->
   let config = config.as_ref();
   trace!("Generating types for i18next resources.");
   let default_locale = config
-    .locales
-    .first()
+    .get_source_locale()
     .map_or("".to_string(), |p| format!("{}{}{}", MAIN_SEPARATOR_STR, p.as_str(), MAIN_SEPARATOR_STR));
   let result = entries
     .iter()
@@ -47,7 +46,7 @@ pub fn generate_types<C: AsRef<Config>>(entries: &[MergeResults], config: C) ->
       entry
         .path
         .strip_prefix(&config.working_dir)
-        .is_ok_and(|s| s.to_str().map_or(false, |p| p.contains(default_locale.as_str())))
+        .is_ok_and(|s| s.to_str().is_some_and(|p| p.contains(default_locale.as_str())))
     })
     .map(|entry| {
       EntryValue {
@@ -177,6 +176,7 @@ mod tests {
         merged: Default::default(),
         backup: Default::default(),
         old_catalog: Default::default(),
+        ..Default::default()
       },
       MergeResults {
         namespace: "another_namespace".to_string(),
@@ -185,6 +185,7 @@ mod tests {
         merged: Default::default(),
         backup: Default::default(),
         old_catalog: Default::default(),
+        ..Default::default()
       },
     ];
 
@@ -0,0 +1,132 @@
+//! This module contains the implementation of the `catalog_diff` function.
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::{helper::get_char_diff::get_char_diff, transform::flatten_entries::flatten_value};
+
+/// A key whose value differs between two catalogs.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ChangedKey {
+  /// The dotted path of the key.
+  pub key: String,
+  /// The value of the key in the old catalog.
+  pub old: String,
+  /// The value of the key in the new catalog.
+  pub new: String,
+  /// The characters diff between the old and the new value.
+  pub diff: String,
+}
+
+/// The differences between two catalogs.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct CatalogDiff {
+  /// The dotted paths of the keys only found in the new catalog.
+  pub added: Vec<String>,
+  /// The dotted paths of the keys only found in the old catalog.
+  pub removed: Vec<String>,
+  /// The keys found in both catalogs with different values.
+  pub changed: Vec<ChangedKey>,
+}
+
+impl CatalogDiff {
+  /// Check if the catalogs are identical.
+  pub fn is_empty(&self) -> bool {
+    self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+  }
+}
+
+/// Computes the differences between two catalogs.
+///
+/// The nested keys are compared by their dotted path, sorted, and the arrays are compared item by item.
+///
+/// # Arguments
+///
+/// * `old` - The old catalog.
+/// * `new` - The new catalog.
+///
+/// # Returns
+///
+/// * `CatalogDiff` - The keys added, removed and changed in the new catalog.
+///
+/// # Example
+///
+/// ```
+/// use i18next_parser_core::catalog_diff;
+/// use serde_json::json;
+///
+/// let diff = catalog_diff(&json!({ "a": { "b": "B" } }), &json!({ "a": { "c": "C" } }));
+/// assert_eq!(diff.added, vec!["a.c"]);
+/// assert_eq!(diff.removed, vec!["a.b"]);
+/// ```
+pub fn catalog_diff(old: &Value, new: &Value) -> CatalogDiff {
+  let mut old_keys = BTreeMap::new();
+  flatten_value("", old, &mut old_keys);
+  let mut new_keys = BTreeMap::new();
+  flatten_value("", new, &mut new_keys);
+
+  let mut diff = CatalogDiff::default();
+  for (key, new_value) in &new_keys {
+    match old_keys.get(key) {
+      None => diff.added.push(key.clone()),
+      Some(old_value) if old_value != new_value => {
+        diff.changed.push(ChangedKey {
+          key: key.clone(),
+          old: old_value.clone(),
+          new: new_value.clone(),
+          diff: get_char_diff(old_value, new_value),
+        })
+      },
+      Some(_) => {},
+    }
+  }
+  diff.removed = old_keys.into_keys().filter(|key| !new_keys.contains_key(key)).collect();
+
+  diff
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn catalog_diff_returns_nothing_for_identical_catalogs() {
+    let catalog = json!({ "a": { "b": "B" }, "c": "C" });
+    assert!(catalog_diff(&catalog, &catalog).is_empty());
+  }
+
+  #[test]
+  fn catalog_diff_identifies_added_keys() {
+    let diff = catalog_diff(&json!({ "a": { "b": "B" } }), &json!({ "a": { "b": "B", "c": { "d": "D" } }, "e": "E" }));
+    assert_eq!(diff, CatalogDiff { added: vec!["a.c.d".into(), "e".into()], ..Default::default() });
+  }
+
+  #[test]
+  fn catalog_diff_identifies_removed_keys() {
+    let diff = catalog_diff(&json!({ "a": { "b": "B", "c": { "d": "D" } }, "e": "E" }), &json!({ "a": { "b": "B" } }));
+    assert_eq!(diff, CatalogDiff { removed: vec!["a.c.d".into(), "e".into()], ..Default::default() });
+  }
+
+  #[test]
+  fn catalog_diff_identifies_changed_keys() {
+    let diff = catalog_diff(&json!({ "a": { "b": "word" } }), &json!({ "a": { "b": "words" } }));
+    assert_eq!(diff, CatalogDiff {
+      changed: vec![ChangedKey {
+        key: "a.b".into(),
+        old: "word".into(),
+        new: "words".into(),
+        diff: get_char_diff("word", "words"),
+      }],
+      ..Default::default()
+    });
+  }
+
+  #[test]
+  fn catalog_diff_handles_a_key_replaced_by_a_nested_key() {
+    let diff = catalog_diff(&json!({ "a": "A" }), &json!({ "a": { "b": "B" } }));
+    assert_eq!(diff, CatalogDiff { added: vec!["a.b".into()], removed: vec!["a".into()], ..Default::default() });
+  }
+}
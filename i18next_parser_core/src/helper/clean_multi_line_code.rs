@@ -5,6 +5,7 @@
 /// `clean_multi_line_code` is a function that takes a string reference as an input and returns a new String.
 /// It removes leading and trailing newline and whitespace characters from the input string.
 /// It also replaces newline characters in the middle of the string with a space.
+/// Whitespace without any newline is kept, so that the spaces separating a text from an adjacent tag are preserved.
 ///
 /// # Arguments
 ///
@@ -58,6 +59,13 @@ mod tests {
     assert_eq!(clean_multi_line_code(input), expected);
   }
 
+  #[test]
+  fn preserves_spaces_adjacent_to_tags() {
+    assert_eq!(clean_multi_line_code("Hello "), "Hello ");
+    assert_eq!(clean_multi_line_code(" world"), " world");
+    assert_eq!(clean_multi_line_code("Hello\n  "), "Hello");
+  }
+
   #[test]
   fn handles_empty_string() {
     let input = "";
@@ -19,6 +19,16 @@ pub struct DotPathToHashResult<'a> {
   pub conflict: Option<Conflict>,
 }
 
+/// Checks if the key of the entry is split on the key separator, a `keySeparator: false` option of the call keeping
+/// the key as a single segment.
+pub(crate) fn splits_key(entry: &Entry) -> bool {
+  entry
+    .i18next_options
+    .as_ref()
+    .and_then(|options| options.get("keySeparator").cloned().flatten())
+    .is_none_or(|key_separator| key_separator != "false")
+}
+
 /// Converts an entry with a dot path to a hash.
 ///
 /// # Arguments
@@ -31,6 +41,8 @@ pub struct DotPathToHashResult<'a> {
 /// # Returns
 ///
 /// * A DotPathToHashResult object.
+///
+/// A key separator escaped with a backslash, like `a\.b`, is kept in its segment instead of nesting the key.
 pub fn dot_path_to_hash<'a>(
   entry: &Entry,
   target: &'a mut Value,
@@ -43,15 +55,16 @@ pub fn dot_path_to_hash<'a>(
     return DotPathToHashResult { target, conflict: None };
   }
 
+  let namespace = entry.namespace.as_ref().unwrap_or(&config.default_namespace);
+  let split_key = splits_key(entry);
+
   let path = {
-    let base_path = entry
-      .namespace
-      .clone()
-      .or(Some(config.default_namespace.clone()))
-      .map(|ns| format!("{ns}{separator}{key}", key = entry.key))
-      .unwrap();
-    let mut path =
-      base_path.replace(r#"\\n"#, "\\n").replace(r#"\\r"#, "\\r").replace(r#"\\t"#, "\\t").replace(r#"\\\\"#, "\\");
+    let base_path = format!("{namespace}{separator}{key}", key = entry.key);
+    let mut path = if config.unescape_defaults {
+      base_path.replace(r#"\\n"#, "\\n").replace(r#"\\r"#, "\\r").replace(r#"\\t"#, "\\t").replace(r#"\\\\"#, "\\")
+    } else {
+      base_path
+    };
 
     if let Some(suffix) = suffix {
       path += suffix;
@@ -67,7 +80,12 @@ pub fn dot_path_to_hash<'a>(
     path
   };
 
-  let segments: Vec<&str> = path.split(separator).collect();
+  let segments: Vec<String> = if split_key {
+    split_unescaped(&path, separator)
+  } else {
+    path.splitn(2, separator).map(|segment| segment.to_string()).collect()
+  };
+  let segments: Vec<&str> = segments.iter().map(|segment| segment.as_str()).collect();
   trace!("Val {:?} {:?} {:?}", &target.yellow(), entry.key.purple(), entry.value.cyan());
 
   let (old_value, mut conflict, inner, last_segment) = lookup_by_key(target, &segments);
@@ -79,7 +97,7 @@ pub fn dot_path_to_hash<'a>(
       if let Some(old_value) = old_value {
         trace!("Values {:?} -> {:?}", old_value.purple(), new_value.purple());
         if old_value != new_value && !old_value.is_empty() {
-          if new_value.is_empty() {
+          if new_value.is_empty() && !config.allow_empty_default {
             trace!("new value is empty, keeping old value {old_value:?}");
             old_value
           } else {
@@ -96,7 +114,7 @@ pub fn dot_path_to_hash<'a>(
         new_value
       }
     })
-    .map(|v| v.trim().into())
+    .map(|v| if config.trim_default_values { v.trim().into() } else { v })
     .unwrap_or_default();
 
   if let Some(namespace) = &entry.namespace {
@@ -109,6 +127,36 @@ pub fn dot_path_to_hash<'a>(
   DotPathToHashResult { target, conflict }
 }
 
+/// Splits a path on the separator, a separator escaped with a backslash, like `a\.b`, being kept in its segment.
+///
+/// # Arguments
+///
+/// * `path` - The path to split.
+/// * `separator` - The separator between the segments of the path.
+///
+/// # Returns
+///
+/// * The segments of the path, without the backslashes escaping the separator.
+pub(crate) fn split_unescaped(path: &str, separator: &str) -> Vec<String> {
+  let escaped = format!("\\{separator}");
+  let mut segments = vec![String::new()];
+  let mut rest = path;
+  while !rest.is_empty() {
+    if let Some(remaining) = rest.strip_prefix(&escaped) {
+      segments.last_mut().unwrap().push_str(separator);
+      rest = remaining;
+    } else if let Some(remaining) = rest.strip_prefix(separator) {
+      segments.push(String::new());
+      rest = remaining;
+    } else {
+      let next = rest.chars().next().unwrap();
+      segments.last_mut().unwrap().push(next);
+      rest = &rest[next.len_utf8()..];
+    }
+  }
+  segments
+}
+
 /// Lookup a value in a JSON object by key.
 ///
 /// # Arguments
@@ -145,6 +193,8 @@ fn lookup_by_key<'a>(
 
 #[cfg(test)]
 mod tests {
+  use std::collections::HashMap;
+
   use pretty_assertions::assert_eq;
   use serde_json::json;
 
@@ -178,6 +228,9 @@ mod tests {
       value: Some("default_value".into()),
       i18next_options: None,
       has_count: true,
+      count_var: None,
+      location: None,
+      locale: None,
     };
     let mut target = json!({
       "namespace": {
@@ -208,6 +261,9 @@ mod tests {
       value: Some("default_value".into()),
       i18next_options: None,
       has_count: true,
+      count_var: None,
+      location: None,
+      locale: None,
     };
     let mut target = json!({});
     let config = Default::default();
@@ -226,6 +282,9 @@ mod tests {
       value: Some("default_value".into()),
       i18next_options: None,
       has_count: true,
+      count_var: None,
+      location: None,
+      locale: None,
     };
     let mut target = json!({});
     let config = Default::default();
@@ -251,6 +310,9 @@ mod tests {
       value: Some("default_value".into()),
       i18next_options: None,
       has_count: true,
+      count_var: None,
+      location: None,
+      locale: None,
     };
     let mut target = json!({
         "namespace": {
@@ -280,6 +342,9 @@ mod tests {
       value: Some("default_value".into()),
       i18next_options: None,
       has_count: true,
+      count_var: None,
+      location: None,
+      locale: None,
     };
     let mut target = json!({
         "namespace": {
@@ -310,6 +375,9 @@ mod tests {
       value: Some("default_value".into()),
       i18next_options: None,
       has_count: true,
+      count_var: None,
+      location: None,
+      locale: None,
     };
     let mut target = json!({
         "namespace": {
@@ -330,4 +398,88 @@ mod tests {
     );
     assert_eq!(result.conflict, Some(Conflict::Value("existing_value".into(), "default_value".into())));
   }
+
+  #[test]
+  fn keeps_existing_value_for_empty_default() {
+    let entry = Entry::new("key", "", "namespace");
+    let mut target = json!({ "namespace": { "key": "existing_value" } });
+    let config = Default::default();
+
+    let result = dot_path_to_hash(&entry, &mut target, None, &config);
+
+    assert_eq!(*result.target, json!({ "namespace": { "key": "existing_value" } }));
+    assert_eq!(result.conflict, None);
+  }
+
+  #[test]
+  fn overwrites_existing_value_for_empty_default_when_allowed() {
+    let entry = Entry::new("key", "", "namespace");
+    let mut target = json!({ "namespace": { "key": "existing_value" } });
+    let config = Config { allow_empty_default: true, ..Default::default() };
+
+    let result = dot_path_to_hash(&entry, &mut target, None, &config);
+
+    assert_eq!(*result.target, json!({ "namespace": { "key": "" } }));
+    assert_eq!(result.conflict, Some(Conflict::Value("existing_value".into(), "".into())));
+  }
+
+  #[test]
+  fn trims_default_values() {
+    let entry = Entry::new("key", " Hello ", "namespace");
+    let mut target = json!({});
+    let config = Default::default();
+
+    let result = dot_path_to_hash(&entry, &mut target, None, &config);
+    assert_eq!(*result.target, json!({ "namespace": { "key": "Hello" } }));
+
+    let mut target = json!({});
+    let config = Config { trim_default_values: false, ..Default::default() };
+
+    let result = dot_path_to_hash(&entry, &mut target, None, &config);
+    assert_eq!(*result.target, json!({ "namespace": { "key": " Hello " } }));
+  }
+
+  #[test]
+  fn unescapes_the_keys_only_when_enabled() {
+    let entry = Entry::new(r#"line\\nbreak"#, "value", "namespace");
+    let mut target = json!({});
+    let config = Default::default();
+
+    let result = dot_path_to_hash(&entry, &mut target, None, &config);
+    assert_eq!(*result.target, json!({ "namespace": { r#"line\nbreak"#: "value" } }));
+
+    let mut target = json!({});
+    let config = Config { unescape_defaults: false, ..Default::default() };
+
+    let result = dot_path_to_hash(&entry, &mut target, None, &config);
+    assert_eq!(*result.target, json!({ "namespace": { r#"line\\nbreak"#: "value" } }));
+  }
+
+  #[test]
+  fn keeps_the_key_as_a_single_segment_without_key_separator() {
+    let options = HashMap::from([("keySeparator".to_string(), Some("false".to_string()))]);
+    let entry = Entry { i18next_options: Some(options), ..Entry::new("a.b", "value", "namespace") };
+    let mut target = json!({});
+
+    let result = dot_path_to_hash(&entry, &mut target, None, &Default::default());
+    assert_eq!(*result.target, json!({ "namespace": { "a.b": "value" } }));
+  }
+
+  #[test]
+  fn test_split_unescaped() {
+    assert_eq!(split_unescaped("ns.a.b", "."), vec!["ns", "a", "b"]);
+    assert_eq!(split_unescaped(r"ns.a\.b", "."), vec!["ns", "a.b"]);
+    assert_eq!(split_unescaped(r"ns::a\::b::c", "::"), vec!["ns", "a::b", "c"]);
+    assert_eq!(split_unescaped(r"ns.a\b", "."), vec!["ns", r"a\b"]);
+    assert_eq!(split_unescaped("ns.", "."), vec!["ns", ""]);
+  }
+
+  #[test]
+  fn keeps_the_escaped_separators_in_a_single_leaf() {
+    let entry = Entry::new(r"a\.b", "value", "namespace");
+    let mut target = json!({});
+
+    let result = dot_path_to_hash(&entry, &mut target, None, &Default::default());
+    assert_eq!(*result.target, json!({ "namespace": { "a.b": "value" } }));
+  }
 }
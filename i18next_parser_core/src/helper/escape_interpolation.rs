@@ -0,0 +1,58 @@
+//! # Escape Interpolation
+//!
+//! `escape_interpolation` is a function that rewrites the interpolations of a text according to the escaping configuration.
+
+use crate::config::InterpolationEscape;
+
+/// `escape_interpolation` is a function that takes a string reference as an input and returns a new String.
+/// It rewrites every interpolation of the text as escaped (`{{value}}`) or unescaped (`{{- value}}`),
+/// or keeps them as written.
+///
+/// # Arguments
+///
+/// * `text` - A string slice that holds the text containing the interpolations.
+/// * `escape` - The escaping to apply to the interpolations.
+///
+/// # Returns
+///
+/// * A String that represents the text with its interpolations rewritten.
+pub fn escape_interpolation(text: &str, escape: &InterpolationEscape) -> String {
+  use regex::Regex;
+
+  match escape {
+    InterpolationEscape::Keep => text.to_string(),
+    InterpolationEscape::Escaped => {
+      let re_unescaped = Regex::new(r"\{\{\s*-\s*").unwrap();
+      re_unescaped.replace_all(text, "{{").into_owned()
+    },
+    InterpolationEscape::Unescaped => {
+      let re_interpolation = Regex::new(r"\{\{\s*(?:-\s*)?").unwrap();
+      re_interpolation.replace_all(text, "{{- ").into_owned()
+    },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn keeps_interpolations_as_written() {
+    let input = "Hello {{- name}} and {{other}}";
+    assert_eq!(escape_interpolation(input, &InterpolationEscape::Keep), input);
+  }
+
+  #[test]
+  fn escapes_unescaped_interpolations() {
+    let input = "Hello {{- name}} and {{other}}";
+    let expected = "Hello {{name}} and {{other}}";
+    assert_eq!(escape_interpolation(input, &InterpolationEscape::Escaped), expected);
+  }
+
+  #[test]
+  fn unescapes_escaped_interpolations() {
+    let input = "Hello {{- name}} and {{other, number}}";
+    let expected = "Hello {{- name}} and {{- other, number}}";
+    assert_eq!(escape_interpolation(input, &InterpolationEscape::Unescaped), expected);
+  }
+}
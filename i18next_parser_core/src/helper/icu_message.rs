@@ -0,0 +1,36 @@
+//! This module contains the implementation of the `is_icu_message` function.
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Matches the start of an ICU argument with plural or select variants, like `{count, plural,`.
+static ICU_REGEX: LazyLock<Regex> =
+  LazyLock::new(|| Regex::new(r"\{\s*[\w.]+\s*,\s*(?:plural|selectordinal|select)\s*,").unwrap());
+
+/// Checks if a text is an ICU message, like `{count, plural, one {# item} other {# items}}`.
+///
+/// The ICU messages hold their own plural and select variants, so they are written verbatim instead of being treated as
+/// i18next interpolations.
+pub(crate) fn is_icu_message(text: &str) -> bool {
+  ICU_REGEX.is_match(text)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn detects_icu_messages() {
+    assert!(is_icu_message("{count, plural, one {# item} other {# items}}"));
+    assert!(is_icu_message("You have {count, plural, =0 {no items} other {{count} items}}"));
+    assert!(is_icu_message("{gender, select, male {He} female {She} other {They}}"));
+    assert!(is_icu_message("{ place , selectordinal, one {#st} other {#th}}"));
+  }
+
+  #[test]
+  fn ignores_i18next_interpolations() {
+    assert!(!is_icu_message("{{count}} items"));
+    assert!(!is_icu_message("Hello {{- name}}, {{value, number}}"));
+    assert!(!is_icu_message("Hello {name}"));
+  }
+}
@@ -0,0 +1,65 @@
+//! This module contains the implementation of the `LineIndex` struct.
+
+/// Maps the offsets of a parsed source to the lines and columns of the file it comes from.
+///
+/// The parsed source is made of chunks copied from the file, like the javascript parts extracted from a MDX file. A
+/// source parsed as is has a single chunk starting at the start of the file.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct LineIndex<'a> {
+  /// the source of the file
+  source: &'a str,
+  /// the offset of the start of each line of the file
+  line_starts: Vec<usize>,
+  /// the chunks of the parsed source, as their offset in the parsed source and in the file, ordered by offset
+  chunks: Vec<(u32, usize)>,
+}
+
+impl<'a> LineIndex<'a> {
+  /// Create the index of a file parsed as is.
+  pub(crate) fn new(source: &'a str) -> Self {
+    Self::with_chunks(source, vec![(0, 0)])
+  }
+
+  /// Create the index of a source made of chunks copied from a file.
+  ///
+  /// # Arguments
+  ///
+  /// * `source` - The source of the file.
+  /// * `chunks` - The offset of each chunk in the parsed source and in the file, ordered by offset.
+  pub(crate) fn with_chunks(source: &'a str, chunks: Vec<(u32, usize)>) -> Self {
+    let line_starts = std::iter::once(0).chain(source.match_indices('\n').map(|(idx, _)| idx + 1)).collect();
+    Self { source, line_starts, chunks }
+  }
+
+  /// Compute the line and column (both starting at 1) in the file of an offset of the parsed source.
+  pub(crate) fn position(&self, offset: u32) -> Option<(usize, usize)> {
+    let chunk = self.chunks.partition_point(|(parsed, _)| *parsed <= offset).checked_sub(1)?;
+    let (parsed, original) = self.chunks[chunk];
+    let offset = original + usize::try_from(offset - parsed).ok()?;
+    let line = self.line_starts.partition_point(|start| *start <= offset);
+    let column = self.source.get(self.line_starts[line.checked_sub(1)?]..offset)?.chars().count() + 1;
+    Some((line, column))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn computes_the_position_of_an_offset() {
+    let index = LineIndex::new("first\nsécond line\n");
+    assert_eq!(index.position(0), Some((1, 1)));
+    assert_eq!(index.position(14), Some((2, 8)));
+    assert_eq!(index.position(100), None);
+    assert_eq!(LineIndex::default().position(0), None);
+  }
+
+  #[test]
+  fn maps_the_offsets_of_the_chunks_to_the_file() {
+    let index = LineIndex::with_chunks("# Title\n\n{t('key')}\n", vec![(0, 0), (1, 10)]);
+    assert_eq!(index.position(0), Some((1, 1)));
+    assert_eq!(index.position(1), Some((3, 2)));
+    assert_eq!(index.position(3), Some((3, 4)));
+  }
+}
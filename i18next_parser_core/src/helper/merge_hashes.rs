@@ -6,7 +6,7 @@ use serde_json::{Map, Value};
 
 use crate::config::Config;
 
-const PLURAL_SUFFIXES: &[&str] = &["zero", "one", "two", "few", "many", "other"];
+pub(crate) const PLURAL_SUFFIXES: &[&str] = &["zero", "one", "two", "few", "many", "other"];
 
 fn is_plural(key: &str) -> bool {
   PLURAL_SUFFIXES.iter().any(|suffix| key.ends_with(suffix))
@@ -16,11 +16,55 @@ fn has_related_plural_key(raw_key: &str, source: &Map<String, Value>) -> bool {
   PLURAL_SUFFIXES.iter().any(|suffix| source.contains_key(&format!("{}{}", raw_key, suffix)))
 }
 
-fn get_singular_form(key: &str, plural_separator: &str) -> String {
+/// Removes the plural suffix from a key, if any.
+pub(crate) fn get_singular_form(key: &str, plural_separator: &str) -> String {
   let plural_regex = Regex::new(&format!(r"(\{}(?:zero|one|two|few|many|other))$", plural_separator)).unwrap();
   plural_regex.replace(key, "").to_string()
 }
 
+/// Splits the context suffix from a key, returning whether a context was found and the key without it.
+fn get_context_base(key: &str, context_separator: &str) -> (bool, String) {
+  if context_separator.is_empty() {
+    return (false, key.to_string());
+  }
+  match key.rsplit_once(context_separator) {
+    Some((raw_key, _)) => (true, raw_key.to_string()),
+    None => (false, key.to_string()),
+  }
+}
+
+/// Strips the plural and context suffixes from a key, returning the key its variants are grouped with.
+pub(crate) fn get_variant_base(key: &str, config: &Config) -> String {
+  let singular = get_singular_form(key, &config.plural_separator);
+  get_context_base(&singular, &config.context_separator).1
+}
+
+/// Merges two arrays element-wise, keeping the source elements and extending them to the length of the existing array.
+/// Objects found at the same index in both arrays are merged together.
+fn merge_arrays(
+  source: &[Value],
+  existing: &[Value],
+  full_key_prefix: &str,
+  reset_and_flag: bool,
+  config: &Config,
+) -> Vec<Value> {
+  let key_separator = &config.key_separator;
+  source
+    .iter()
+    .enumerate()
+    .map(|(idx, value)| {
+      match existing.get(idx) {
+        Some(target_value) if target_value.is_object() && value.is_object() => {
+          let nested_prefix = format!("{full_key_prefix}{idx}{key_separator}");
+          merge_hashes(Some(value), target_value, None, &nested_prefix, reset_and_flag, config).new
+        },
+        _ => value.clone(),
+      }
+    })
+    .chain(existing.iter().skip(source.len()).cloned())
+    .collect()
+}
+
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct MergeResult {
   /// The merged hash
@@ -39,7 +83,7 @@ pub struct MergeResult {
 /// This function takes an existing JSON object and merges it with a source JSON object.
 /// If a key exists in both, the value from the source object is used.
 /// If a key exists only in the source object, it is added to the existing object.
-/// The function also handles nested JSON objects.
+/// The function also handles nested JSON objects, and arrays which are merged element-wise.
 ///
 /// # Arguments
 ///
@@ -101,6 +145,7 @@ pub fn merge_hashes(
 
   let key_separator = &config.key_separator;
   let plural_separator = &config.plural_separator;
+  let context_separator = &config.context_separator;
   let reset_values_map = reset_values.and_then(|v| v.as_object()).map_or_else(Map::new, |v| v.clone());
 
   if let Some(Value::Object(source_map)) = source {
@@ -162,6 +207,13 @@ pub fn merge_hashes(
           reset.insert(key.clone(), Value::Bool(true));
           reset_count += 1;
         },
+        Some(Value::Array(target_values)) if value.is_array() => {
+          debug!("Merging array key: {}", key.yellow());
+          let source_values = value.as_array().map_or(&[][..], Vec::as_slice);
+          let nested_prefix = format!("{full_key_prefix}{key}{key_separator}");
+          *target_values = merge_arrays(source_values, target_values, &nested_prefix, reset_and_flag, config);
+          merge_count += 1;
+        },
         Some(target_value) => {
           debug!("Replacing key: {} from {} to {}", key.purple(), target_value.cyan(), value.cyan());
           *target_value = value.clone();
@@ -172,14 +224,7 @@ pub fn merge_hashes(
           let singular_key = get_singular_form(key, plural_separator);
           let plural_match = key != &singular_key;
 
-          const CONTEXT_SEPARATOR: char = '_';
-          let regex = Regex::new(
-            format!("\\{context_separator}([^\\{context_separator}]+)?$", context_separator = CONTEXT_SEPARATOR)
-              .as_str(),
-          )
-          .unwrap();
-          let context_match = regex.is_match(&singular_key);
-          let raw_key = regex.replace(&singular_key, "").to_string();
+          let (context_match, raw_key) = get_context_base(&singular_key, context_separator);
 
           if (context_match && existing.contains_key(&raw_key))
             || (plural_match && has_related_plural_key(&format!("{}{}", singular_key, plural_separator), &existing))
@@ -391,6 +436,42 @@ mod tests {
     assert_eq!(result.old_count, 2);
   }
 
+  #[test]
+  fn should_restores_context_keys_with_a_custom_context_separator() {
+    let source = json!({ "key1": "", "key1-context": "value1", "key2_context": "value2" });
+    let target = json!({ "key1": "", "key2": "" });
+    let config = Config { context_separator: "-".into(), ..Default::default() };
+
+    let result = merge_hashes(Some(&source), &target, None, "", false, &config);
+
+    assert_eq!(result.new, json!({ "key1": "", "key1-context": "value1", "key2": "" }));
+    assert_eq!(result.old, json!({ "key2_context": "value2" }));
+    assert_eq!(result.merge_count, 1);
+    assert_eq!(result.pull_count, 1);
+    assert_eq!(result.old_count, 1);
+  }
+
+  #[test]
+  fn should_merge_arrays_element_wise() {
+    let source = json!({ "list": ["Un", "Deux", "Trois"] });
+    let target = json!({ "list": ["One", "Two"] });
+
+    let result = default_merge_hashes(&source, &target);
+
+    assert_eq!(result.new, json!({ "list": ["Un", "Deux", "Trois"] }));
+    assert_eq!(result.old, json!({}));
+    assert_eq!(result.merge_count, 1);
+
+    let source = json!({ "list": ["Un", { "label": "Deux" }] });
+    let target = json!({ "list": ["One", { "label": "Two", "hint": "" }, "Three"] });
+
+    let result = default_merge_hashes(&source, &target);
+
+    assert_eq!(result.new, json!({ "list": ["Un", { "label": "Deux", "hint": "" }, "Three"] }));
+    assert_eq!(result.old, json!({}));
+    assert_eq!(result.merge_count, 1);
+  }
+
   #[test]
   fn should_works_with_deep_objects() {
     let source = json!({
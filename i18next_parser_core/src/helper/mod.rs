@@ -1,6 +1,11 @@
 //! This module contains helper functions that are used in multiple places in the codebase.
+pub mod catalog_diff;
 pub mod clean_multi_line_code;
 pub mod dot_path_to_hash;
+pub mod escape_interpolation;
 pub mod get_char_diff;
+pub(crate) mod icu_message;
+pub(crate) mod line_index;
 pub mod merge_hashes;
+pub(crate) mod natural_cmp;
 mod skip_last;
@@ -0,0 +1,50 @@
+//! This module contains the implementation of the `natural_cmp` function.
+use std::{cmp::Ordering, iter::Peekable, str::Chars};
+
+/// Takes the run of ASCII digits at the start of the characters.
+fn take_digits(chars: &mut Peekable<Chars<'_>>) -> String {
+  let mut digits = String::new();
+  while let Some(c) = chars.next_if(char::is_ascii_digit) {
+    digits.push(c);
+  }
+  digits
+}
+
+/// Compares two strings, the runs of digits being compared by their numeric value.
+///
+/// The runs of digits with the same value are ordered by their number of leading zeros, so that only identical
+/// strings are equal.
+pub(crate) fn natural_cmp(a: &str, b: &str) -> Ordering {
+  let mut a = a.chars().peekable();
+  let mut b = b.chars().peekable();
+  loop {
+    let ordering = match (a.peek(), b.peek()) {
+      (None, None) => return Ordering::Equal,
+      (None, Some(_)) => return Ordering::Less,
+      (Some(_), None) => return Ordering::Greater,
+      (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+        let (x, y) = (take_digits(&mut a), take_digits(&mut b));
+        let (trimmed_x, trimmed_y) = (x.trim_start_matches('0'), y.trim_start_matches('0'));
+        trimmed_x.len().cmp(&trimmed_y.len()).then_with(|| trimmed_x.cmp(trimmed_y)).then_with(|| x.len().cmp(&y.len()))
+      },
+      (Some(_), Some(_)) => a.next().cmp(&b.next()),
+    };
+    if ordering != Ordering::Equal {
+      return ordering;
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn should_compare_digits_by_value() {
+    let mut keys = vec!["item10", "item2", "item1", "item", "item02", "other"];
+    keys.sort_by(|a, b| natural_cmp(a, b));
+    assert_eq!(keys, vec!["item", "item1", "item2", "item02", "item10", "other"]);
+  }
+}
@@ -1,3 +1,5 @@
+mod check_consistency;
+mod check_default_values;
 mod config;
 mod file;
 mod helper;
@@ -5,16 +7,48 @@ mod is_empty;
 mod macros;
 mod merger;
 mod print;
+mod profile;
 mod transform;
 mod visitor;
 
-pub use config::Config;
-pub use file::{parser::parse_directory::parse_directory, writer::write_to_file};
-pub use helper::{clean_multi_line_code::clean_multi_line_code, merge_hashes::merge_hashes};
+pub use check_consistency::{check_consistency, find_inconsistencies, Inconsistency};
+pub use check_default_values::{check_default_values, find_missing_default_values};
+pub use config::{Config, ConfigFileFormat};
+pub use file::{
+  parser::parse_directory::{explain_globs, find_input_files, parse_directory, parse_files, GlobExplanation},
+  writer::write_to_file,
+};
+pub use helper::{
+  catalog_diff::{catalog_diff, CatalogDiff, ChangedKey},
+  clean_multi_line_code::clean_multi_line_code,
+  merge_hashes::{merge_hashes, MergeResult},
+};
 pub use is_empty::IsEmpty;
-pub use merger::{merge_all_values::merge_all_values, merge_results::MergeResults};
-pub use print::print_config::print_config;
-pub use visitor::Entry;
+pub use merger::{
+  apply_entries::apply_entries,
+  merge_all_values::merge_all_values,
+  merge_results::{CatalogChanges, MergeResults},
+};
+pub use print::{
+  print_config::print_config,
+  print_count::{
+    check_namespace_sizes,
+    count_by_locale,
+    find_oversized_namespaces,
+    print_summary,
+    LocaleCounts,
+    OversizedNamespace,
+  },
+  print_globs::print_glob_explanations,
+  print_profile::print_profile,
+};
+pub use profile::{Phase, Profile};
+pub use transform::{
+  consolidate_catalogs::consolidate_catalogs,
+  export_pot::export_pot,
+  flatten_entries::flatten_entries,
+};
+pub use visitor::{Entry, EntryLocation};
 
 #[cfg(feature = "generate_types")]
 mod generate_types;
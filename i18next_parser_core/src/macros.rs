@@ -53,6 +53,7 @@ mod log_time_tests {
   /// Verifies that the log_time macro correctly handles a function that panics.
   #[test]
   #[should_panic(expected = "Intentional panic")]
+  #[allow(unused_variables)]
   fn handles_panic_within_function() {
     let panic_function = || {
       panic!("Intentional panic");
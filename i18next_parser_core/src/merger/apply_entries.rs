@@ -0,0 +1,109 @@
+//! Applies the extracted entries to an in-memory catalog, without reading nor writing any file.
+use serde_json::{Map, Value};
+
+use crate::{
+  config::Config,
+  helper::merge_hashes::{merge_hashes, MergeResult},
+  transform::transform_entries::transform_entries,
+  Entry,
+};
+
+/// Applies the entries to a catalog of a locale, as they would be merged into the catalog files, without touching disk.
+///
+/// The catalog holds the keys of each namespace, like `{ "namespace": { "key": "value" } }`. The namespaces without
+/// entries are kept as is, the other ones are replaced by their merged keys. As for the catalog files, the values of
+/// the source locale differing from the extracted default values are reset.
+///
+/// # Arguments
+///
+/// * `catalog` - The catalog to apply the entries to, updated with the merged keys.
+/// * `entries` - The extracted entries.
+/// * `locale` - The locale of the catalog.
+/// * `config` - The configuration.
+///
+/// # Returns
+///
+/// * The merge result, holding the merged catalog and the keys removed from it, by namespace, along with the counts
+///   summed across the namespaces.
+///
+/// # Errors
+///
+/// This function returns an error if the entries cannot be transformed, like a key nested too deeply.
+///
+/// # Example
+///
+/// ```
+/// use i18next_parser_core::{apply_entries, Config, Entry};
+/// use serde_json::json;
+///
+/// let mut catalog = json!({ "translation": { "title": "Title" } });
+/// let entries = vec![Entry { key: "title".into(), ..Default::default() }];
+///
+/// let config = Config { locales: vec!["en".into(), "fr".into()], ..Default::default() };
+///
+/// let result = apply_entries(&mut catalog, &entries, "fr", &config).unwrap();
+/// assert_eq!(result.merge_count, 1);
+/// assert_eq!(catalog, json!({ "translation": { "title": "Title" } }));
+/// ```
+pub fn apply_entries(
+  catalog: &mut Value,
+  entries: &[Entry],
+  locale: &str,
+  config: &Config,
+) -> color_eyre::Result<MergeResult> {
+  let transformed = transform_entries(entries, locale, config)?;
+  let is_default = config.get_source_locale().is_some_and(|source_locale| source_locale == locale);
+
+  let mut result = MergeResult { new: catalog.clone(), ..Default::default() };
+  let (mut old, mut reset) = (Map::new(), Map::new());
+  for (namespace, keys) in transformed.value.as_object().into_iter().flatten() {
+    let full_key_prefix = format!("{}{}", namespace, config.key_separator);
+    let merged = merge_hashes(catalog.get(namespace), keys, None, &full_key_prefix, is_default, config);
+    result.new[namespace] = merged.new;
+    if merged.old.as_object().is_some_and(|keys| !keys.is_empty()) {
+      old.insert(namespace.clone(), merged.old);
+    }
+    if merged.reset.as_object().is_some_and(|keys| !keys.is_empty()) {
+      reset.insert(namespace.clone(), merged.reset);
+    }
+    result.merge_count += merged.merge_count;
+    result.pull_count += merged.pull_count;
+    result.old_count += merged.old_count;
+    result.reset_count += merged.reset_count;
+  }
+  result.old = Value::Object(old);
+  result.reset = Value::Object(reset);
+  *catalog = result.new.clone();
+
+  Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn applies_the_entries_to_the_catalog() {
+    let mut catalog = json!({
+      "translation": { "title": "Existing title", "removed": "Removed" },
+      "other": { "key": "Untouched" }
+    });
+    let entries = vec![Entry::new("title", "Title", "translation"), Entry::new("added", "Added", "translation")];
+    let config = Config { locales: vec!["en".into(), "fr".into()], ..Default::default() };
+
+    let result = apply_entries(&mut catalog, &entries, "fr", &config).unwrap();
+
+    let expected = json!({
+      "translation": { "title": "Existing title", "added": "Added" },
+      "other": { "key": "Untouched" }
+    });
+    assert_eq!(catalog, expected);
+    assert_eq!(result.new, expected);
+    assert_eq!(result.old, json!({ "translation": { "removed": "Removed" } }));
+    assert_eq!(result.merge_count, 1);
+    assert_eq!(result.old_count, 1);
+  }
+}
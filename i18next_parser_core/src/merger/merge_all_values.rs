@@ -1,19 +1,60 @@
+use std::{cmp::Reverse, path::PathBuf};
+
 use color_eyre::{eyre::eyre, owo_colors::OwoColorize};
+use serde_json::Value;
 use tracing::instrument;
 
 use crate::{
   config::Config,
+  file::catalog::read_file_into_serde,
   log_time,
   merger::merge_results::{merge_results, MergeResults},
   transform::transform_entries::{transform_entries, TransformEntriesResult},
   Entry,
 };
 
+/// Sorts the entries by descending source file path, then by descending position in the file, after the entries
+/// without location.
+///
+/// The value of the last entry wins among duplicated keys, so the entry found first in the lowest path is the one
+/// kept. The sort is stable so the entries without location keep their relative order, which makes the entry kept
+/// independent of the order in which the files have been discovered.
+fn sort_entries(entries: &mut [Entry]) {
+  fn location(entry: &Entry) -> Option<Reverse<(&PathBuf, u32)>> {
+    entry.location.as_ref().map(|location| Reverse((&location.file_path, location.span.start)))
+  }
+  entries.sort_by(|a, b| location(a).cmp(&location(b)));
+}
+
+/// Reads the seed catalogs of the locale, from `config.seed` relative to the working directory.
+fn read_seed(locale: &str, config: &Config) -> Option<Value> {
+  let seed = config.seed.as_ref()?;
+  read_file_into_serde(&config.working_dir.join(seed.to_string_lossy().replace("$LOCALE", locale)))
+}
+
+/// Fills the empty values of the catalog with the values found at the same path in the seed.
+fn apply_seed(catalog: &mut Value, seed: &Value) {
+  let (Some(catalog), Some(seed)) = (catalog.as_object_mut(), seed.as_object()) else { return };
+  for (key, value) in catalog.iter_mut() {
+    let Some(seed) = seed.get(key) else { continue };
+    match value {
+      Value::String(text) if text.is_empty() => {
+        if let Some(seed) = seed.as_str() {
+          *text = seed.to_string();
+        }
+      },
+      Value::Object(_) => apply_seed(value, seed),
+      _ => {},
+    }
+  }
+}
+
 /// Merges all translation values across different locales based on the provided entries and configuration.
 ///
 /// This function processes a vector of `Entry` objects, each representing a translation entry, and merges
 /// them into a structured format suitable for writing to JSON files. The merging process is influenced by
 /// the configuration specified in `config`, particularly the locales to be considered.
+/// The keys without default value take the one of the seed of their locale, when `config.seed` is set.
 ///
 /// # Arguments
 ///
@@ -42,6 +83,9 @@ use crate::{
 ///   namespace: Some("default".into()),
 ///   key: "key".into(),
 ///   has_count: false,
+///   count_var: None,
+///   location: None,
+///   locale: None,
 ///   value: Some("value".into()),
 ///   i18next_options: None,
 /// }];
@@ -51,18 +95,22 @@ use crate::{
 /// assert!(result.is_ok());
 /// ```
 #[instrument(skip_all, err, target = "instrument")]
-pub fn merge_all_values(entries: Vec<Entry>, config: &Config) -> color_eyre::Result<Vec<MergeResults>> {
+pub fn merge_all_values(mut entries: Vec<Entry>, config: &Config) -> color_eyre::Result<Vec<MergeResults>> {
   tracing::info!(target: "instrument_log", "Merging {} entries across locales", entries.len().cyan());
+  sort_entries(&mut entries);
   log_time!("Preparing entries to write", {
     let locales = &config.locales;
-    let default_locale = &config.locales.first().ok_or(eyre!("No locales found in the configuration."))?;
+    let default_locale = &config.get_source_locale().ok_or(eyre!("No locales found in the configuration."))?;
 
     let result = locales
       .iter()
       .filter_map(|locale| {
         let entry = transform_entries(&entries, locale, config);
         match entry {
-          Ok(TransformEntriesResult { unique_count, unique_plurals_count, value, locale }) if value.is_object() => {
+          Ok(TransformEntriesResult { unique_count, unique_plurals_count, mut value, locale }) if value.is_object() => {
+            if let Some(seed) = read_seed(&locale, config) {
+              apply_seed(&mut value, &seed);
+            }
             let catalog = value.as_object().unwrap();
             let result = catalog
               .iter()
@@ -77,12 +125,14 @@ pub fn merge_all_values(entries: Vec<Entry>, config: &Config) -> color_eyre::Res
                   config,
                 )
               })
-              .collect::<Vec<_>>();
+              .collect::<color_eyre::Result<Vec<_>>>();
             Some(result)
           },
           _ => None,
         }
       })
+      .collect::<color_eyre::Result<Vec<_>>>()?
+      .into_iter()
       .flatten()
       .collect::<Vec<_>>();
 
@@ -96,7 +146,7 @@ mod tests {
   use serde_json::json;
 
   use super::*;
-  use crate::{config::Config, helper::merge_hashes::MergeResult, Entry};
+  use crate::{config::Config, helper::merge_hashes::MergeResult, Entry, EntryLocation};
 
   #[test]
   fn merge_all_values_simple_case() {
@@ -104,6 +154,9 @@ mod tests {
       namespace: Some("default".into()),
       key: "key".into(),
       has_count: false,
+      count_var: None,
+      location: None,
+      locale: None,
       value: Some("value".into()),
       i18next_options: None,
     }];
@@ -128,10 +181,97 @@ mod tests {
         reset_count: 0,
       },
       old_catalog: json!({}),
+      unique_count: 1,
+      unique_plurals_count: 0,
     }];
     assert_eq!(result, expected);
   }
 
+  #[test]
+  fn merge_all_values_writes_forced_locale_entries_only_in_their_locale() {
+    let entries = vec![Entry::new_with_value("shared", "Shared"), Entry {
+      locale: Some("fr".into()),
+      ..Entry::new_with_value("french", "Français")
+    }];
+    let config = Config { locales: vec!["en".into(), "fr".into()], ..Default::default() };
+
+    let result = merge_all_values(entries, &config).unwrap();
+
+    let catalogs = result.iter().map(|result| (result.locale.as_str(), &result.merged.new)).collect::<Vec<_>>();
+    assert_eq!(catalogs, vec![
+      ("en", &json!({ "shared": "Shared" })),
+      ("fr", &json!({ "shared": "Shared", "french": "Français" })),
+    ]);
+  }
+
+  #[test]
+  fn merge_all_values_takes_the_missing_default_values_from_the_seed() {
+    let dir = tempdir::TempDir::new("merge_all_values").unwrap();
+    std::fs::create_dir_all(dir.path().join("seeds")).unwrap();
+    let seed = json!({ "translation": { "hello": "Bonjour", "title": "Titre" } });
+    std::fs::write(dir.path().join("seeds/fr.json"), seed.to_string()).unwrap();
+    let config = Config {
+      working_dir: dir.path().to_path_buf(),
+      output: "$LOCALE/$NAMESPACE.json".into(),
+      locales: vec!["en".into(), "fr".into()],
+      seed: Some("seeds/$LOCALE.json".into()),
+      ..Default::default()
+    };
+    let entries = vec![Entry::empty("hello"), Entry::new_with_value("title", "Title")];
+
+    let result = merge_all_values(entries, &config).unwrap();
+
+    let catalogs = result.iter().map(|result| (result.locale.as_str(), &result.merged.new)).collect::<Vec<_>>();
+    assert_eq!(catalogs, vec![
+      ("en", &json!({ "hello": "", "title": "Title" })),
+      ("fr", &json!({ "hello": "Bonjour", "title": "Title" })),
+    ]);
+  }
+
+  #[test]
+  fn merge_all_values_resets_the_values_of_the_source_locale() {
+    let dir = tempdir::TempDir::new("merge_all_values").unwrap();
+    for (locale, value) in [("en", "Old hello"), ("fr", "Ancien bonjour")] {
+      std::fs::create_dir_all(dir.path().join(locale)).unwrap();
+      std::fs::write(dir.path().join(locale).join("translation.json"), json!({ "hello": value }).to_string()).unwrap();
+    }
+    let config = Config {
+      working_dir: dir.path().to_path_buf(),
+      output: "$LOCALE/$NAMESPACE.json".into(),
+      locales: vec!["en".into(), "fr".into()],
+      source_locale: Some("fr".into()),
+      ..Default::default()
+    };
+
+    let result = merge_all_values(vec![Entry::new_with_value("hello", "Bonjour")], &config).unwrap();
+
+    let catalogs = result
+      .iter()
+      .map(|result| (result.locale.as_str(), &result.merged.new, result.merged.reset_count))
+      .collect::<Vec<_>>();
+    assert_eq!(catalogs, vec![("en", &json!({ "hello": "Old hello" }), 0), ("fr", &json!({ "hello": "Bonjour" }), 1),]);
+  }
+
+  #[test]
+  fn merge_all_values_is_independent_of_the_files_order() {
+    let entry = |file: &str, start: u32, value: &str| {
+      let location =
+        EntryLocation { file_path: file.into(), span: oxc_span::Span::new(start, start + 10), position: None };
+      Entry { location: Some(location), ..Entry::new_with_value("key", value) }
+    };
+    let config = Config { locales: vec!["en".into()], ..Default::default() };
+
+    let mut entries = vec![entry("src/b.tsx", 0, "B"), entry("src/a.tsx", 20, "A2"), entry("src/a.tsx", 5, "A1")];
+    sort_entries(&mut entries);
+    let values = entries.iter().map(|entry| entry.value.clone().unwrap()).collect::<Vec<_>>();
+    assert_eq!(values, vec!["B", "A2", "A1"]);
+
+    let first = merge_all_values(vec![entry("src/a.tsx", 0, "A"), entry("src/b.tsx", 0, "B")], &config).unwrap();
+    let second = merge_all_values(vec![entry("src/b.tsx", 0, "B"), entry("src/a.tsx", 0, "A")], &config).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first[0].merged.new, json!({ "key": "A" }));
+  }
+
   #[test]
   fn merge_all_values_with_valid_entries_and_config() {
     let entries = vec![
@@ -139,6 +279,9 @@ mod tests {
         namespace: Some("default".into()),
         key: "key1".into(),
         has_count: false,
+        count_var: None,
+        location: None,
+        locale: None,
         value: Some("value1".into()),
         i18next_options: None,
       },
@@ -146,6 +289,9 @@ mod tests {
         namespace: Some("default".into()),
         key: "key2".into(),
         has_count: true,
+        count_var: None,
+        location: None,
+        locale: None,
         value: Some("value2".into()),
         i18next_options: None,
       },
@@ -153,6 +299,9 @@ mod tests {
         namespace: Some("custom".into()),
         key: "key3".into(),
         has_count: false,
+        count_var: None,
+        location: None,
+        locale: None,
         value: Some("value3".into()),
         i18next_options: None,
       },
@@ -179,6 +328,8 @@ mod tests {
           reset_count: 0,
         },
         old_catalog: json!({}),
+        unique_count: 1,
+        unique_plurals_count: 0,
       },
       MergeResults {
         namespace: "default".into(),
@@ -195,6 +346,8 @@ mod tests {
           reset_count: 0,
         },
         old_catalog: json!({}),
+        unique_count: 3,
+        unique_plurals_count: 2,
       },
     ];
     assert_eq!(result, expected);
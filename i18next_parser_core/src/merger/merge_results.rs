@@ -1,4 +1,8 @@
-use std::{collections::HashMap, path::PathBuf, str::FromStr};
+use std::{
+  collections::HashMap,
+  path::{Component, Path, PathBuf},
+  str::FromStr,
+};
 
 use color_eyre::owo_colors::OwoColorize;
 use log::trace;
@@ -21,6 +25,8 @@ use crate::{
 /// - `backup`: The path to the backup of the original translation file before merging.
 /// - `merged`: The result of the merge operation, including counts of new, removed, and unchanged translations.
 /// - `old_catalog`: The original translation data before the merge.
+/// - `unique_count`: The number of unique keys extracted for the namespace.
+/// - `unique_plurals_count`: The number of plural keys extracted for the namespace.
 #[derive(Debug, Default, Eq, PartialEq)]
 pub struct MergeResults {
   /// The namespace of the translation, used to categorize translations.
@@ -35,6 +41,123 @@ pub struct MergeResults {
   pub merged: MergeResult,
   /// The original translation data before the merge.
   pub old_catalog: Value,
+  /// The number of unique keys extracted for the namespace, plural forms included.
+  pub unique_count: usize,
+  /// The number of plural keys extracted for the namespace.
+  pub unique_plurals_count: usize,
+}
+
+/// Creates the results of a merge holding a catalog, for the tests.
+#[cfg(test)]
+pub(crate) fn merge_result(namespace: &str, locale: &str, new: Value) -> MergeResults {
+  MergeResults {
+    namespace: namespace.into(),
+    locale: locale.into(),
+    merged: MergeResult { new, ..Default::default() },
+    ..Default::default()
+  }
+}
+
+/// Counts the leaves of a value, the nested objects being walked.
+fn count_leaves(value: &Value) -> usize {
+  match value {
+    Value::Object(map) => map.values().map(count_leaves).sum(),
+    Value::Null => 0,
+    _ => 1,
+  }
+}
+
+impl MergeResults {
+  /// Checks if the merge adds keys to the catalog, the extracted keys not being all merged with existing ones.
+  pub fn has_additions(&self) -> bool {
+    self.unique_count > self.merged.merge_count + self.merged.reset_count
+  }
+
+  /// Checks if the merge removes keys from the catalog, the keys kept by `keep_removed` and the reset ones excluded.
+  pub fn has_removals(&self) -> bool {
+    count_leaves(&self.merged.old) > count_leaves(&self.merged.reset)
+  }
+
+  /// Checks if the merge changes the catalog, keys being added, removed or reset.
+  pub fn is_changed(&self) -> bool {
+    self.has_additions() || self.has_removals() || self.merged.reset_count > 0
+  }
+}
+
+/// The kind of changes applied to the catalogs by a run.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CatalogChanges {
+  /// No key has been added nor removed.
+  Unchanged,
+  /// Keys have been added, none has been removed.
+  Additions,
+  /// Keys have been removed.
+  Removals,
+  /// Values have been reset, no key has been added nor removed.
+  Resets,
+}
+
+impl CatalogChanges {
+  /// Computes the changes from the merge results of every catalog.
+  pub fn from_results(results: &[MergeResults]) -> Self {
+    let has_removals = results.iter().any(MergeResults::has_removals);
+    let has_additions = results.iter().any(MergeResults::has_additions);
+    let has_resets = results.iter().any(|result| result.merged.reset_count > 0);
+    match (has_removals, has_additions, has_resets) {
+      (true, _, _) => CatalogChanges::Removals,
+      (false, true, _) => CatalogChanges::Additions,
+      (false, false, true) => CatalogChanges::Resets,
+      (false, false, false) => CatalogChanges::Unchanged,
+    }
+  }
+}
+
+/// Computes the path of the old catalog (backup) associated to a catalog path.
+///
+/// The backup file name is the catalog file stem followed by `config.old_catalog_suffix`, and it is placed
+/// inside `config.old_catalog_dir` (relative to the catalog directory) when one is configured.
+/// When `config.backup_dir` is set, the backup is moved under that directory, mirroring its path from the working directory.
+fn get_backup_path(path: &Path, config: &Config) -> PathBuf {
+  let filename = {
+    let filename = path.file_stem().and_then(|o| o.to_str()).unwrap_or_default();
+    let extension = path.extension().and_then(|o| o.to_str()).unwrap_or_default();
+    format!("{}{}.{}", filename, config.old_catalog_suffix, extension)
+  };
+  let backup = match &config.old_catalog_dir {
+    Some(dir) => path.parent().map_or_else(|| PathBuf::from(dir), |parent| parent.join(dir)).join(filename),
+    None => path.with_file_name(filename),
+  };
+  match &config.backup_dir {
+    Some(dir) => {
+      // mirror the catalog structure inside the backup directory
+      let relative = backup.strip_prefix(&config.working_dir).map(Path::to_path_buf).unwrap_or_else(|_| {
+        backup.components().filter(|component| matches!(component, Component::Normal(_))).collect()
+      });
+      config.working_dir.join(dir).join(relative)
+    },
+    None => backup,
+  }
+}
+
+/// Keeps the existing catalog, replacing only the subtree at the scope key path by the one of the merged catalog.
+fn scope_catalog(existing: Option<&Value>, merged: &Value, scope: &str, key_separator: &str) -> Value {
+  let path = scope.split(key_separator).collect::<Vec<_>>();
+  let scoped = path.iter().try_fold(merged, |value, key| value.get(key));
+
+  let mut catalog = existing.filter(|value| value.is_object()).cloned().unwrap_or(Value::Object(Default::default()));
+  let (last, parents) = path.split_last().expect("split always yields a key");
+  let mut parent = &mut catalog;
+  for key in parents {
+    let Some(object) = parent.as_object_mut() else { return catalog };
+    parent = object.entry(key.to_string()).or_insert_with(|| Value::Object(Default::default()));
+  }
+  if let Some(object) = parent.as_object_mut() {
+    match scoped {
+      Some(scoped) => object.insert(last.to_string(), scoped.clone()),
+      None => object.remove(*last),
+    };
+  }
+  catalog
 }
 
 /// Merges translation data from different sources and produces a `MergeResults` struct.
@@ -54,6 +177,9 @@ pub struct MergeResults {
 ///
 /// # Returns
 /// A `MergeResults` struct containing the results of the merge operation.
+///
+/// # Errors
+/// Returns an error when the output of the configuration contains an unknown token.
 pub fn merge_results<C: AsRef<Config>>(
   locale: &str,
   namespace: &str,
@@ -62,19 +188,17 @@ pub fn merge_results<C: AsRef<Config>>(
   unique_plurals_count: &HashMap<String, usize>,
   is_default: bool,
   config: C,
-) -> MergeResults {
+) -> color_eyre::Result<MergeResults> {
   let config = config.as_ref();
-  let output = config.get_output();
+  let output = config.get_output()?;
   let path = output.replace("$LOCALE", locale).replace("$NAMESPACE", namespace);
   trace!("Path for output {}: {}", output.yellow(), path.yellow());
   let path = PathBuf::from_str(&path).unwrap_or_else(|_| panic!("Unable to find path {path:?}"));
-  // get backup file name
-  let filename = {
-    let filename = path.file_stem().and_then(|o| o.to_str()).unwrap_or_default();
-    let extension = path.extension().and_then(|o| o.to_str()).unwrap_or_default();
-    format!("{}_old.{}", filename, extension)
+  let path = match config.namespace_formats.get(namespace) {
+    Some(format) => path.with_extension(format.extension()),
+    None => path,
   };
-  let backup = path.with_file_name(filename);
+  let backup = get_backup_path(&path, config);
   trace!("File path: {}", path.display().yellow());
   trace!("Backup path: {}", backup.display().yellow());
 
@@ -86,7 +210,18 @@ pub fn merge_results<C: AsRef<Config>>(
   trace!("Value: {:?} -> {:?}", value.cyan(), old_value.cyan());
 
   let full_key_prefix = format!("{}{}", namespace, config.key_separator);
-  let merged = merge_hashes(value.as_ref(), catalog, old_value, &full_key_prefix, is_default, config);
+  let merged = match &config.scope {
+    Some(scope) => {
+      // only the keys of the scope are merged, the other keys of the existing catalog being kept as they are
+      let scoped = |value: &Value| scope_catalog(None, value, scope, &config.key_separator);
+      let existing = value.as_ref().map(scoped);
+      let mut merged =
+        merge_hashes(existing.as_ref(), &scoped(catalog), old_value, &full_key_prefix, is_default, config);
+      merged.new = scope_catalog(value.as_ref(), &merged.new, scope, &config.key_separator);
+      merged
+    },
+    None => merge_hashes(value.as_ref(), catalog, old_value, &full_key_prefix, is_default, config),
+  };
   let old_merged = merge_hashes(old_value, &merged.new, None, &full_key_prefix, false, &Config {
     keep_removed: false,
     ..Default::default()
@@ -96,7 +231,16 @@ pub fn merge_results<C: AsRef<Config>>(
     print_counts(locale, namespace, unique_count, unique_plurals_count, &merged, &old_merged, config);
   }
 
-  MergeResults { namespace: namespace.to_string(), locale: locale.to_string(), path, backup, merged, old_catalog }
+  Ok(MergeResults {
+    namespace: namespace.to_string(),
+    locale: locale.to_string(),
+    path,
+    backup,
+    merged,
+    old_catalog,
+    unique_count: unique_count.get(namespace).copied().unwrap_or_default(),
+    unique_plurals_count: unique_plurals_count.get(namespace).copied().unwrap_or_default(),
+  })
 }
 
 #[cfg(test)]
@@ -139,7 +283,8 @@ mod tests {
     let is_default = true;
     let config = Config { locales: vec![locale.into()], output, ..Default::default() };
 
-    let result = merge_results(locale, namespace, &catalog, &unique_count, &unique_plurals_count, is_default, config);
+    let result =
+      merge_results(locale, namespace, &catalog, &unique_count, &unique_plurals_count, is_default, config).unwrap();
     drop(dir);
     println!("Results: {:#?}", result);
     let merged = result.merged;
@@ -147,4 +292,162 @@ mod tests {
     assert_eq!(merged.old, value, "the old value do not match");
     assert_eq!(merged.merge_count, 0, "the merge count do not match");
   }
+
+  #[test_log::test]
+  fn merge_results_only_updates_the_scope() {
+    let value = json!({ "common": { "dialog": { "title": "Title", "removed": "Removed" }, "other": "Other" } });
+    let locale = "en";
+    let namespace = "default";
+    let dir = TempDir::new("merge_results").unwrap();
+    let output = init_test(&dir, locale, namespace, &value).unwrap();
+    let catalog = json!({ "common": { "dialog": { "title": "Title", "body": "Body" }, "discovered": "Discovered" } });
+    let config =
+      Config { locales: vec![locale.into()], output, scope: Some("common.dialog".into()), ..Default::default() };
+
+    let result = merge_results(locale, namespace, &catalog, &HashMap::new(), &HashMap::new(), true, &config).unwrap();
+
+    assert_eq!(
+      result.merged.new,
+      json!({ "common": { "dialog": { "title": "Title", "body": "Body" }, "other": "Other" } })
+    );
+    assert_eq!(result.merged.old, json!({ "common": { "dialog": { "removed": "Removed" } } }));
+    assert_eq!(result.merged.old_count, 1, "the keys out of the scope are not removed");
+    assert_eq!(result.old_catalog, result.merged.old);
+
+    let catalog = json!({ "common": { "dialog": { "title": "Title", "removed": "Removed" } } });
+    let result = merge_results(locale, namespace, &catalog, &HashMap::new(), &HashMap::new(), true, &config).unwrap();
+    assert_eq!(result.merged.old_count, 0, "the keys out of the scope are not removed");
+    assert_eq!(result.merged.new, value);
+  }
+
+  #[test_log::test]
+  fn get_backup_path_uses_default_suffix() {
+    let config = Config::default();
+    let backup = get_backup_path(Path::new("locales/en/default.json"), &config);
+    assert_eq!(backup, PathBuf::from("locales/en/default_old.json"));
+  }
+
+  #[test_log::test]
+  fn get_backup_path_honors_custom_suffix() {
+    let config = Config { old_catalog_suffix: ".old".into(), ..Default::default() };
+    let backup = get_backup_path(Path::new("locales/en/default.json"), &config);
+    assert_eq!(backup, PathBuf::from("locales/en/default.old.json"));
+  }
+
+  #[test_log::test]
+  fn get_backup_path_honors_custom_dir() {
+    let config = Config { old_catalog_suffix: "".into(), old_catalog_dir: Some("_old".into()), ..Default::default() };
+    let backup = get_backup_path(Path::new("locales/en/default.json"), &config);
+    assert_eq!(backup, PathBuf::from("locales/en/_old/default.json"));
+  }
+
+  #[test_log::test]
+  fn get_backup_path_honors_backup_dir() {
+    let config =
+      Config { working_dir: PathBuf::from("project"), backup_dir: Some("backup".into()), ..Default::default() };
+    let backup = get_backup_path(Path::new("project/locales/en/default.json"), &config);
+    assert_eq!(backup, PathBuf::from("project/backup/locales/en/default_old.json"));
+  }
+
+  #[test_log::test]
+  fn merge_results_is_changed() {
+    let result = |unique_count, merged| MergeResults { unique_count, merged, ..Default::default() };
+
+    assert!(!result(0, Default::default()).is_changed());
+    assert!(!result(2, MergeResult { merge_count: 2, pull_count: 1, ..Default::default() }).is_changed());
+    assert!(result(3, MergeResult { merge_count: 2, ..Default::default() }).is_changed());
+    let removed = || json!({ "removed": "Removed" });
+    assert!(result(2, MergeResult { merge_count: 2, old: removed(), old_count: 1, ..Default::default() }).is_changed());
+    assert!(!result(2, MergeResult { merge_count: 2, old_count: 1, ..Default::default() }).is_changed());
+    assert!(result(2, MergeResult { merge_count: 2, reset_count: 1, ..Default::default() }).is_changed());
+  }
+
+  #[test_log::test]
+  fn catalog_changes_from_results() {
+    let result = |unique_count, merge_count, old_count| {
+      let old = if old_count > 0 { json!({ "removed": "Removed" }) } else { json!({}) };
+      MergeResults {
+        unique_count,
+        merged: MergeResult { merge_count, old_count, old, ..Default::default() },
+        ..Default::default()
+      }
+    };
+
+    assert_eq!(CatalogChanges::from_results(&[]), CatalogChanges::Unchanged);
+    assert_eq!(CatalogChanges::from_results(&[result(2, 2, 0)]), CatalogChanges::Unchanged);
+    assert_eq!(CatalogChanges::from_results(&[result(2, 2, 0), result(3, 2, 0)]), CatalogChanges::Additions);
+    assert_eq!(CatalogChanges::from_results(&[result(3, 2, 0), result(2, 2, 1)]), CatalogChanges::Removals);
+  }
+
+  #[test_log::test]
+  fn catalog_changes_ignore_the_kept_keys() {
+    let value = json!({ "title": "Title", "removed": "Removed" });
+    let locale = "en";
+    let namespace = "default";
+    let dir = TempDir::new("merge_results").unwrap();
+    let output = init_test(&dir, locale, namespace, &value).unwrap();
+    let catalog = json!({ "title": "Title" });
+    let unique_count = HashMap::from([(namespace.to_string(), 1)]);
+    let config = Config { locales: vec![locale.into()], output, ..Default::default() };
+
+    let result = merge_results(locale, namespace, &catalog, &unique_count, &HashMap::new(), false, &config).unwrap();
+    assert_eq!(CatalogChanges::from_results(&[result]), CatalogChanges::Removals);
+
+    let config = Config { keep_removed: true, ..config };
+    let result = merge_results(locale, namespace, &catalog, &unique_count, &HashMap::new(), false, &config).unwrap();
+    assert_eq!(result.merged.old_count, 1, "the kept key is still counted");
+    assert!(!result.is_changed());
+    assert_eq!(CatalogChanges::from_results(&[result]), CatalogChanges::Unchanged);
+
+    let catalog = json!({ "title": "New title", "removed": "Removed" });
+    let unique_count = HashMap::from([(namespace.to_string(), 2)]);
+    let result = merge_results(locale, namespace, &catalog, &unique_count, &HashMap::new(), true, &config).unwrap();
+    assert_eq!(result.merged.reset_count, 1);
+    assert!(result.is_changed());
+    assert_eq!(CatalogChanges::from_results(&[result]), CatalogChanges::Resets);
+  }
+
+  #[test_log::test]
+  fn merge_results_backup_honors_backup_dir() {
+    let dir = TempDir::new("merge_results").unwrap();
+    let config = Config {
+      working_dir: dir.path().to_path_buf(),
+      locales: vec!["en".into(), "fr".into()],
+      output: "locales/$LOCALE/$NAMESPACE.json".into(),
+      backup_dir: Some("backup".into()),
+      ..Default::default()
+    };
+
+    for locale in ["en", "fr"] {
+      let result =
+        merge_results(locale, "default", &json!({}), &HashMap::new(), &HashMap::new(), true, &config).unwrap();
+      assert_eq!(result.backup, dir.path().join("backup").join("locales").join(locale).join("default_old.json"));
+    }
+  }
+
+  #[test_log::test]
+  fn merge_results_keeps_the_locale_subtags_in_the_path() {
+    let dir = TempDir::new("merge_results").unwrap();
+    let output = dir.path().join("locales/$LOCALE/$NAMESPACE.json").to_str().unwrap().to_string();
+    let config = Config { locales: vec!["zh-Hant".into(), "pt-BR".into()], output, ..Default::default() };
+
+    for locale in ["zh-Hant", "pt-BR"] {
+      let result =
+        merge_results(locale, "default", &json!({}), &HashMap::new(), &HashMap::new(), true, &config).unwrap();
+      assert_eq!(result.path, dir.path().join("locales").join(locale).join("default.json"));
+    }
+  }
+
+  #[test_log::test]
+  fn merge_results_backup_honors_custom_suffix() {
+    let locale = "en";
+    let namespace = "default";
+    let dir = TempDir::new("merge_results").unwrap();
+    let output = dir.path().join("locales/$LOCALE/$NAMESPACE.json").to_str().unwrap().to_string();
+    let config =
+      Config { locales: vec![locale.into()], output, old_catalog_suffix: ".old".into(), ..Default::default() };
+
+    let result = merge_results(locale, namespace, &json!({}), &HashMap::new(), &HashMap::new(), true, &config).unwrap();
+    assert_eq!(result.backup, dir.path().join("locales/en/default.old.json"));
+  }
 }
@@ -1,2 +1,3 @@
+pub mod apply_entries;
 pub mod merge_all_values;
 pub mod merge_results;
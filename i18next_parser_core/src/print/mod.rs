@@ -1,3 +1,5 @@
 //! Print module for core crate.
 pub mod print_config;
 pub mod print_count;
+pub mod print_globs;
+pub mod print_profile;
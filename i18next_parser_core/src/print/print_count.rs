@@ -1,8 +1,102 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+use color_eyre::{eyre::bail, owo_colors::OwoColorize};
+use log::warn;
 use tracing::info;
 
-use crate::{config::Config, helper::merge_hashes::MergeResult};
+use crate::{
+  config::Config,
+  helper::merge_hashes::MergeResult,
+  merger::merge_results::MergeResults,
+  transform::flatten_entries::flatten_value,
+};
+
+/// The number of keys extracted for a locale, across all its namespaces.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct LocaleCounts {
+  /// The locale of the counted keys.
+  pub locale: String,
+  /// The number of unique keys, plural forms included.
+  pub unique_count: usize,
+  /// The number of plural keys.
+  pub unique_plurals_count: usize,
+}
+
+/// Sums the extracted keys counts of the merge results per locale, keeping the locales order.
+pub fn count_by_locale(results: &[MergeResults]) -> Vec<LocaleCounts> {
+  let mut counts: Vec<LocaleCounts> = Vec::new();
+  for result in results {
+    let index = match counts.iter().position(|counts| counts.locale == result.locale) {
+      Some(index) => index,
+      None => {
+        counts.push(LocaleCounts { locale: result.locale.clone(), ..Default::default() });
+        counts.len() - 1
+      },
+    };
+    counts[index].unique_count += result.unique_count;
+    counts[index].unique_plurals_count += result.unique_plurals_count;
+  }
+  counts
+}
+
+/// A merged namespace holding more keys than `max_keys_per_namespace`.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct OversizedNamespace {
+  /// The locale of the catalog.
+  pub locale: String,
+  /// The namespace of the catalog.
+  pub namespace: String,
+  /// The number of keys of the merged catalog.
+  pub key_count: usize,
+}
+
+/// Lists the merged namespaces holding more keys than `max_keys_per_namespace`.
+pub fn find_oversized_namespaces(results: &[MergeResults], config: &Config) -> Vec<OversizedNamespace> {
+  let Some(max_keys) = config.max_keys_per_namespace else {
+    return vec![];
+  };
+
+  results
+    .iter()
+    .filter_map(|result| {
+      let mut flat = BTreeMap::new();
+      flatten_value("", &result.merged.new, &mut flat);
+      (flat.len() > max_keys).then(|| {
+        OversizedNamespace { locale: result.locale.clone(), namespace: result.namespace.clone(), key_count: flat.len() }
+      })
+    })
+    .collect()
+}
+
+/// Reports the namespaces exceeding `max_keys_per_namespace`, before the catalogs are written.
+///
+/// # Errors
+///
+/// This function returns an error if a namespace exceeds the limit and `fail_on_warnings` is enabled.
+pub fn check_namespace_sizes(results: &[MergeResults], config: &Config) -> color_eyre::Result<()> {
+  let oversized = find_oversized_namespaces(results, config);
+  for OversizedNamespace { locale, namespace, key_count } in &oversized {
+    warn!(
+      "[{}] {} holds {} keys, more than the {} allowed",
+      locale,
+      namespace.bright_yellow(),
+      key_count.yellow(),
+      config.max_keys_per_namespace.unwrap_or_default()
+    );
+  }
+  if config.fail_on_warnings && !oversized.is_empty() {
+    bail!("{} namespaces exceed the maximum number of keys", oversized.len());
+  }
+
+  Ok(())
+}
+
+/// Prints the number of extracted keys per locale.
+pub fn print_summary(results: &[MergeResults]) {
+  for LocaleCounts { locale, unique_count, unique_plurals_count } in count_by_locale(results) {
+    info!(layer = "count", "[{}] Unique keys: {} ({} are plurals)", locale, unique_count, unique_plurals_count);
+  }
+}
 
 pub fn print_counts(
   locale: &str,
@@ -34,3 +128,54 @@ pub fn print_counts(
   }
   info!(layer = "count", "");
 }
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+  use crate::{merge_all_values, Entry};
+
+  #[test]
+  fn count_by_locale_sums_namespaces() {
+    let entries = vec![
+      Entry::new_with_ns("key1", "default"),
+      Entry { has_count: true, ..Entry::new_with_ns("key2", "default") },
+      Entry::new_with_ns("key3", "custom"),
+    ];
+    let config = Config { locales: vec!["en".into(), "fr".into()], ..Default::default() };
+
+    let results = merge_all_values(entries, &config).unwrap();
+
+    assert_eq!(count_by_locale(&results), vec![
+      LocaleCounts { locale: "en".into(), unique_count: 4, unique_plurals_count: 2 },
+      LocaleCounts { locale: "fr".into(), unique_count: 5, unique_plurals_count: 3 },
+    ]);
+  }
+
+  #[test]
+  fn check_namespace_sizes_reports_namespaces_past_the_limit() {
+    let entries = vec![
+      Entry::new_with_ns("key1", "default"),
+      Entry::new_with_ns("key2", "default"),
+      Entry::new_with_ns("key3", "custom"),
+    ];
+    let config = Config { max_keys_per_namespace: Some(1), ..Default::default() };
+
+    let results = merge_all_values(entries, &config).unwrap();
+
+    assert_eq!(find_oversized_namespaces(&results, &config), vec![OversizedNamespace {
+      locale: "en".into(),
+      namespace: "default".into(),
+      key_count: 2,
+    }]);
+    assert!(check_namespace_sizes(&results, &config).is_ok());
+
+    let config = Config { fail_on_warnings: true, ..config };
+    assert!(check_namespace_sizes(&results, &config).is_err());
+
+    let config = Config { max_keys_per_namespace: Some(2), ..config };
+    assert!(find_oversized_namespaces(&results, &config).is_empty());
+    assert!(check_namespace_sizes(&results, &config).is_ok());
+  }
+}
@@ -0,0 +1,20 @@
+//! Print the files matched by the input globs.
+use color_eyre::owo_colors::OwoColorize;
+
+use crate::file::parser::parse_directory::GlobExplanation;
+
+/// Print each input glob with the files it matched and ignored.
+pub fn print_glob_explanations(explanations: &[GlobExplanation]) {
+  for explanation in explanations {
+    println!("{}", explanation.glob.bright_cyan());
+    if explanation.matched.is_empty() && explanation.ignored.is_empty() {
+      println!("  {}", "no files matched".italic().bright_black());
+    }
+    for file in &explanation.matched {
+      println!("  {} {}", "matched".green(), file.display());
+    }
+    for file in &explanation.ignored {
+      println!("  {} {}", "ignored".yellow(), file.display());
+    }
+  }
+}
@@ -0,0 +1,15 @@
+//! Print the duration of the phases of a run.
+use color_eyre::owo_colors::OwoColorize;
+
+use crate::profile::Profile;
+
+/// Print the duration of each phase of the profile.
+pub fn print_profile(profile: &Profile) {
+  let width = profile.phases.iter().map(|phase| phase.name.len()).max().unwrap_or_default();
+  eprintln!("  {}", "Profile".bright_cyan());
+  for phase in &profile.phases {
+    let duration = phase.duration.as_secs_f64() * 1000.0;
+    eprintln!("  {:width$} {:>10.2}ms", phase.name.bright_cyan(), duration);
+  }
+  eprintln!()
+}
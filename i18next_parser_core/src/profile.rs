@@ -0,0 +1,49 @@
+//! This module records the duration of the phases of a run.
+use std::time::{Duration, Instant};
+
+use crate::log_time;
+
+/// The duration of a phase of a run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Phase {
+  /// The name of the phase.
+  pub name: String,
+  /// The time spent in the phase.
+  pub duration: Duration,
+}
+
+/// Records the duration of the phases of a run, in the order they are run.
+#[derive(Debug, Default)]
+pub struct Profile {
+  /// The phases recorded.
+  pub phases: Vec<Phase>,
+}
+
+impl Profile {
+  /// Run the function as the phase with the given name, recording its duration.
+  pub fn record<T, F: FnOnce() -> T>(&mut self, name: &str, func: F) -> T {
+    let start = Instant::now();
+    let result = log_time!(name, func());
+    self.phases.push(Phase { name: name.to_string(), duration: start.elapsed() });
+    result
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+
+  use super::*;
+
+  #[test]
+  fn should_record_the_phases_in_order() {
+    let mut profile = Profile::default();
+
+    let value = profile.record("first", || 42);
+    let result: Result<(), &str> = profile.record("second", || Err("error"));
+
+    assert_eq!(value, 42);
+    assert!(result.is_err());
+    assert_eq!(profile.phases.iter().map(|phase| phase.name.as_str()).collect::<Vec<_>>(), vec!["first", "second"]);
+  }
+}
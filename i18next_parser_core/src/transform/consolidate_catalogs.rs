@@ -0,0 +1,69 @@
+//! Consolidates the merged catalogs into a single JSON object, as expected when importing them into another system.
+use serde_json::{Map, Value};
+
+use crate::merger::merge_results::MergeResults;
+
+/// Consolidates the catalogs of every locale and namespace into a `{ locale: { namespace: { ...keys } } }` object.
+///
+/// A namespace extracted for a single locale only appears under that locale.
+///
+/// # Arguments
+///
+/// * `results` - The merge results of every catalog.
+///
+/// # Returns
+///
+/// * `Value` - The consolidated catalogs.
+///
+/// # Example
+///
+/// ```
+/// use i18next_parser_core::{consolidate_catalogs, MergeResults};
+/// use serde_json::json;
+///
+/// let mut result = MergeResults { namespace: "common".into(), locale: "en".into(), ..Default::default() };
+/// result.merged.new = json!({ "button": { "save": "Save" } });
+///
+/// let consolidated = consolidate_catalogs(&[result]);
+/// assert_eq!(consolidated, json!({ "en": { "common": { "button": { "save": "Save" } } } }));
+/// ```
+pub fn consolidate_catalogs(results: &[MergeResults]) -> Value {
+  let mut consolidated = Map::new();
+  for result in results {
+    let locale = consolidated.entry(result.locale.clone()).or_insert_with(|| Value::Object(Map::new()));
+    if let Value::Object(locale) = locale {
+      locale.insert(result.namespace.clone(), result.merged.new.clone());
+    }
+  }
+
+  Value::Object(consolidated)
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+  use crate::merger::merge_results::merge_result;
+
+  #[test]
+  fn should_consolidate_every_locale_and_namespace() {
+    let results = vec![
+      merge_result("common", "en", json!({ "button": { "save": "Save" } })),
+      merge_result("home", "en", json!({ "title": "Home" })),
+      merge_result("common", "fr", json!({ "button": { "save": "Enregistrer" } })),
+      merge_result("home", "fr", json!({ "title": "Accueil" })),
+    ];
+
+    let consolidated = consolidate_catalogs(&results);
+
+    assert_eq!(
+      consolidated,
+      json!({
+        "en": { "common": { "button": { "save": "Save" } }, "home": { "title": "Home" } },
+        "fr": { "common": { "button": { "save": "Enregistrer" } }, "home": { "title": "Accueil" } },
+      })
+    );
+  }
+}
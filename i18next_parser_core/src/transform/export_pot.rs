@@ -0,0 +1,145 @@
+//! Exports the merged catalogs as a gettext template (POT), as expected by gettext based translation workflows.
+use std::collections::BTreeMap;
+
+use crate::{
+  config::Config,
+  helper::merge_hashes::get_singular_form,
+  merger::merge_results::MergeResults,
+  transform::flatten_entries::flatten_value,
+};
+
+/// Escapes a text as the content of a gettext string.
+fn escape_pot_string(text: &str) -> String {
+  text.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "\\r").replace('\t', "\\t")
+}
+
+/// Exports the catalogs of the default locale as a gettext template.
+///
+/// Every key is written as a `msgid` within the context of its namespace, with comments holding its namespace, the
+/// base key of its plural forms and its default value.
+/// The default locale is the source locale of the configuration, the `source_locale` or else the first of the
+/// `locales`.
+///
+/// # Arguments
+///
+/// * `results` - The merge results of every catalog.
+/// * `config` - The configuration, providing the source locale and the plural separator.
+///
+/// # Returns
+///
+/// * `String` - The content of the POT file.
+///
+/// # Example
+///
+/// ```
+/// use i18next_parser_core::{export_pot, Config, MergeResults};
+/// use serde_json::json;
+///
+/// let mut result = MergeResults { namespace: "common".into(), locale: "en".into(), ..Default::default() };
+/// result.merged.new = json!({ "button": { "save": "Save" } });
+///
+/// let pot = export_pot(&[result], &Config::default());
+/// assert!(pot.contains("msgctxt \"common\"\nmsgid \"button.save\"\nmsgstr \"\"\n"));
+/// ```
+pub fn export_pot(results: &[MergeResults], config: &Config) -> String {
+  let mut pot = String::from("msgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n");
+  let Some(default_locale) = config.get_source_locale() else {
+    return pot;
+  };
+
+  for result in results.iter().filter(|result| &result.locale == default_locale) {
+    let mut catalog = BTreeMap::new();
+    flatten_value("", &result.merged.new, &mut catalog);
+    for (key, value) in catalog {
+      let namespace = &result.namespace;
+      pot.push_str(&format!("\n#. namespace: {namespace}\n"));
+      let singular = get_singular_form(&key, &config.plural_separator);
+      if singular != key {
+        pot.push_str(&format!("#. plural of: {singular}\n"));
+      }
+      if !value.is_empty() {
+        pot.push_str(&format!("#. default: {}\n", escape_pot_string(&value)));
+      }
+      pot.push_str(&format!("msgctxt \"{}\"\n", escape_pot_string(namespace)));
+      pot.push_str(&format!("msgid \"{}\"\n", escape_pot_string(&key)));
+      pot.push_str("msgstr \"\"\n");
+    }
+  }
+
+  pot
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+  use crate::merger::merge_results::merge_result;
+
+  #[test]
+  fn should_export_every_key_of_the_default_locale() {
+    let results = vec![
+      merge_result("common", "en", json!({ "button": { "save": "Save \"now\"" }, "item_one": "", "item_other": "" })),
+      merge_result("home", "en", json!({ "title": "Home" })),
+      merge_result("common", "fr", json!({ "button": { "save": "Enregistrer" } })),
+    ];
+
+    let pot = export_pot(&results, &Config::default());
+
+    assert_eq!(
+      pot,
+      r#"msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+
+#. namespace: common
+#. default: Save \"now\"
+msgctxt "common"
+msgid "button.save"
+msgstr ""
+
+#. namespace: common
+#. plural of: item
+msgctxt "common"
+msgid "item_one"
+msgstr ""
+
+#. namespace: common
+#. plural of: item
+msgctxt "common"
+msgid "item_other"
+msgstr ""
+
+#. namespace: home
+#. default: Home
+msgctxt "home"
+msgid "title"
+msgstr ""
+"#
+    );
+  }
+
+  #[test]
+  fn should_export_the_keys_of_the_source_locale() {
+    let results = vec![
+      merge_result("common", "en", json!({ "title": "Title" })),
+      merge_result("common", "fr", json!({ "titre": "" })),
+    ];
+    let config =
+      Config { locales: vec!["en".into(), "fr".into()], source_locale: Some("fr".into()), ..Default::default() };
+
+    let pot = export_pot(&results, &config);
+
+    assert!(pot.contains("msgid \"titre\"\n"));
+    assert!(!pot.contains("msgid \"title\"\n"));
+  }
+
+  #[test]
+  fn should_export_only_the_header_without_results() {
+    assert_eq!(
+      export_pot(&[], &Config::default()),
+      "msgid \"\"\nmsgstr \"\"\n\"Content-Type: text/plain; charset=UTF-8\\n\"\n"
+    );
+  }
+}
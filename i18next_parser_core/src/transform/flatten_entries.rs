@@ -0,0 +1,109 @@
+//! Flattens the merged catalogs into a list of keys, as expected by translation management systems.
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+
+use crate::merger::merge_results::MergeResults;
+
+/// Flattens a JSON value, appending every leaf to the map with its dotted path.
+pub(crate) fn flatten_value(prefix: &str, value: &Value, flat: &mut BTreeMap<String, String>) {
+  match value {
+    Value::Object(map) => {
+      for (key, value) in map {
+        let path = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+        flatten_value(&path, value, flat);
+      }
+    },
+    Value::Array(values) => {
+      for (idx, value) in values.iter().enumerate() {
+        flatten_value(&format!("{prefix}.{idx}"), value, flat);
+      }
+    },
+    Value::String(value) => {
+      flat.insert(prefix.to_string(), value.clone());
+    },
+    Value::Null => {
+      flat.insert(prefix.to_string(), String::new());
+    },
+    value => {
+      flat.insert(prefix.to_string(), value.to_string());
+    },
+  }
+}
+
+/// Flattens the catalogs of the default locale into `namespace:dotted.key` keys mapped to their default value.
+///
+/// The default locale is the locale of the first merge result, as `merge_all_values` processes the locales in the
+/// configured order.
+///
+/// # Arguments
+///
+/// * `results` - The merge results of every catalog.
+///
+/// # Returns
+///
+/// * `BTreeMap<String, String>` - The flattened keys, sorted, mapped to their default value.
+///
+/// # Example
+///
+/// ```
+/// use i18next_parser_core::{flatten_entries, MergeResults};
+/// use serde_json::json;
+///
+/// let mut result = MergeResults { namespace: "common".into(), locale: "en".into(), ..Default::default() };
+/// result.merged.new = json!({ "button": { "save": "Save" } });
+///
+/// let flat = flatten_entries(&[result]);
+/// assert_eq!(flat.get("common:button.save"), Some(&"Save".to_string()));
+/// ```
+pub fn flatten_entries(results: &[MergeResults]) -> BTreeMap<String, String> {
+  let mut flat = BTreeMap::new();
+  let Some(default_locale) = results.first().map(|result| &result.locale) else {
+    return flat;
+  };
+
+  for result in results.iter().filter(|result| &result.locale == default_locale) {
+    let mut catalog = BTreeMap::new();
+    flatten_value("", &result.merged.new, &mut catalog);
+    flat.extend(catalog.into_iter().map(|(key, value)| (format!("{}:{}", result.namespace, key), value)));
+  }
+
+  flat
+}
+
+#[cfg(test)]
+mod tests {
+  use pretty_assertions::assert_eq;
+  use serde_json::json;
+
+  use super::*;
+  use crate::merger::merge_results::merge_result;
+
+  #[test]
+  fn should_flatten_the_default_locale_catalogs() {
+    let results = vec![
+      merge_result("common", "en", json!({ "button": { "save": "Save", "cancel": "" }, "items": ["One", "Two"] })),
+      merge_result("home", "en", json!({ "title": "Home", "count": 2 })),
+      merge_result("common", "fr", json!({ "button": { "save": "Enregistrer", "cancel": "Annuler" } })),
+    ];
+
+    let flat = flatten_entries(&results);
+
+    assert_eq!(
+      flat,
+      BTreeMap::from([
+        ("common:button.cancel".to_string(), "".to_string()),
+        ("common:button.save".to_string(), "Save".to_string()),
+        ("common:items.0".to_string(), "One".to_string()),
+        ("common:items.1".to_string(), "Two".to_string()),
+        ("home:count".to_string(), "2".to_string()),
+        ("home:title".to_string(), "Home".to_string()),
+      ])
+    );
+  }
+
+  #[test]
+  fn should_flatten_nothing_without_results() {
+    assert!(flatten_entries(&[]).is_empty());
+  }
+}
@@ -1,4 +1,7 @@
 //! Transform module.
+pub mod consolidate_catalogs;
+pub mod export_pot;
+pub mod flatten_entries;
 pub mod plural;
 pub mod transfer_values;
 pub mod transform_entries;
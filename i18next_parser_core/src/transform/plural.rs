@@ -1,9 +1,11 @@
 //! This module contains the plural rules and resolver.
-use std::collections::HashMap;
+use std::{cell::RefCell, collections::HashMap};
 
 use color_eyre::{eyre::eyre, Result};
 use intl_pluralrules::{PluralRuleType, PluralRules};
 
+use crate::config::PluralSuffixStyle;
+
 /// Cleans the provided code by replacing underscores with hyphens.
 ///
 /// # Arguments
@@ -48,7 +50,13 @@ fn create_rules(sets: Vec<PluralSet>) -> HashMap<&'static str, RuleValue> {
     |n| (n != 1) as u32, // 2
     |_| 0,               // 3
     |n| {
-      (n % 10 == 1 && n % 100 != 11) as u32 + (n % 10 >= 2 && n % 10 <= 4 && (n % 100 < 10 || n % 100 >= 20)) as u32 * 2
+      if n % 10 == 1 && n % 100 != 11 {
+        0
+      } else if n % 10 >= 2 && n % 10 <= 4 && (n % 100 < 10 || n % 100 >= 20) {
+        1
+      } else {
+        2
+      }
     }, // 4
     |n| {
       if n == 0 {
@@ -226,18 +234,33 @@ type Rules = HashMap<&'static str, RuleValue>;
 ///
 /// * `rules` - A Rules hashmap containing the plural rules.
 /// * `simplify_plural_suffix` - A boolean indicating whether to simplify the plural suffix.
+/// * `suffixes` - A cache of the suffixes already resolved per code.
 pub struct PluralResolver {
   rules: Rules,
   simplify_plural_suffix: bool,
   prepend: Option<String>,
   version: I18NVersion,
+  overrides: HashMap<String, Vec<String>>,
+  suffixes: RefCell<HashMap<String, Vec<String>>>,
 }
 
 /// A struct representing the supported i18n version.
 #[derive(Default)]
 pub enum I18NVersion {
+  /// Plural categories suffixes (`_one`, `_other`), resolved from the CLDR rules.
   #[default]
   V4,
+  /// Numeric plural indices suffixes (`_0`, `_1`), resolved from the hand-written rule tables.
+  V3,
+}
+
+impl From<&PluralSuffixStyle> for I18NVersion {
+  fn from(value: &PluralSuffixStyle) -> Self {
+    match value {
+      PluralSuffixStyle::Category => I18NVersion::V4,
+      PluralSuffixStyle::Numeric => I18NVersion::V3,
+    }
+  }
 }
 
 impl Default for PluralResolver {
@@ -305,10 +328,27 @@ impl PluralResolver {
 
     let rules = create_rules(sets);
 
-    Self { rules, simplify_plural_suffix, prepend, version }
+    Self {
+      rules,
+      simplify_plural_suffix,
+      prepend,
+      version,
+      overrides: Default::default(),
+      suffixes: Default::default(),
+    }
+  }
+
+  /// Returns the PluralResolver using the provided plural categories instead of the computed rules of their locale.
+  ///
+  /// # Arguments
+  ///
+  /// * `overrides` - A map of the plural categories by locale.
+  pub fn with_overrides(mut self, overrides: HashMap<String, Vec<String>>) -> Self {
+    self.overrides = overrides;
+    self
   }
 
-  /// Returns the plural rule for the provided code.
+  /// Returns the plural rule for the provided code, falling back to the rule of its language without region subtag.
   ///
   /// # Arguments
   ///
@@ -319,7 +359,10 @@ impl PluralResolver {
   /// * An Option containing a reference to a RuleValue.
   fn get_rule(&self, code: &str) -> Option<&RuleValue> {
     let cleaned_code = get_cleaned_code(code);
-    self.rules.get(cleaned_code.as_str())
+    self.rules.get(cleaned_code.as_str()).or_else(|| {
+      let language = cleaned_code.split('-').next()?;
+      self.rules.get(language)
+    })
   }
 
   /// Returns a vector of strings representing the suffixes for the provided code.
@@ -332,16 +375,51 @@ impl PluralResolver {
   ///
   /// * A vector of Strings representing the suffixes.
   pub fn get_suffixes(&self, code: &str) -> Result<Vec<String>> {
-    #[allow(unreachable_patterns)]
+    if let Some(suffixes) = self.suffixes.borrow().get(code) {
+      return Ok(suffixes.clone());
+    }
+
+    let suffixes = self.resolve_suffixes(code)?;
+    self.suffixes.borrow_mut().insert(code.to_string(), suffixes.clone());
+    Ok(suffixes)
+  }
+
+  /// Returns the overridden categories of the provided code, falling back to the ones of its language.
+  ///
+  /// The codes are compared cleaned, `pt_BR` being overridden as `pt-BR`.
+  fn get_overrides(&self, code: &str) -> Option<&Vec<String>> {
+    let code = get_cleaned_code(code);
+    let language = code.split('-').next().unwrap_or_default();
+    let find = |wanted: &str| {
+      self
+        .overrides
+        .iter()
+        .find(|(overridden, _)| get_cleaned_code(overridden) == wanted)
+        .map(|(_, categories)| categories)
+    };
+    find(&code).or_else(|| find(language))
+  }
+
+  /// Resolves the suffixes for the provided code, without using the cache.
+  fn resolve_suffixes(&self, code: &str) -> Result<Vec<String>> {
+    if let Some(categories) = self.get_overrides(code) {
+      let prepend = self.prepend.clone().unwrap_or_default();
+      return Ok(categories.iter().map(|category| format!("{prepend}{category}")).collect());
+    }
+
     match self.version {
       I18NVersion::V4 => {
-        let lang: unic_langid::LanguageIdentifier = code.parse()?;
-        let plural_rules = PluralRules::create(lang, PluralRuleType::CARDINAL).map_err(|e| eyre!(e))?;
+        let lang: unic_langid::LanguageIdentifier = get_cleaned_code(code).parse()?;
+        // the region and script subtags fall back to the rules of the language when they have none of their own
+        let language = unic_langid::LanguageIdentifier::from_parts(lang.language, None, None, &[]);
+        let plural_rules = PluralRules::create(lang, PluralRuleType::CARDINAL)
+          .or_else(|_| PluralRules::create(language, PluralRuleType::CARDINAL))
+          .map_err(|e| eyre!(e))?;
         let result = plural_rules.resolved_options();
         let prepend = self.prepend.clone().unwrap_or_default();
         Ok(result.iter().map(|n| format!("{prepend}{n}")).collect::<Vec<String>>())
       },
-      _ => {
+      I18NVersion::V3 => {
         let result = match self.get_rule(code) {
           Some((numbers, _)) => numbers.iter().map(|&n| self.get_suffix(code, n)).collect(),
           None => vec![],
@@ -366,24 +444,18 @@ impl PluralResolver {
     match self.get_rule(code) {
       Some((rules, plural_func)) => {
         let idx = plural_func(count);
-        if self.simplify_plural_suffix {
+        let suffix = if self.simplify_plural_suffix {
           match idx {
-            1 => "".to_string(),
+            1 => return String::new(),
             2 => "plural".to_string(),
             _ => idx.to_string(),
           }
+        } else if (idx as usize) < rules.len() {
+          idx.to_string()
         } else {
-          let rule = rules.get(idx as usize);
-          fn return_suffix(prepend: Option<String>, suffix: Option<&u32>) -> String {
-            match (prepend, suffix) {
-              (Some(prepend), Some(suffix)) => format!("{prepend}{suffix}"),
-              (None, Some(suffix)) => suffix.to_string(),
-              _ => String::new(),
-            }
-          }
-
-          return_suffix(self.prepend.clone(), rule)
-        }
+          return String::new();
+        };
+        format!("{}{suffix}", self.prepend.clone().unwrap_or_default())
       },
       None => String::new(),
     }
@@ -425,6 +497,19 @@ mod tests {
       assert!(!resolver.simplify_plural_suffix);
     }
 
+    #[test_log::test]
+    fn get_suffixes_caches_resolved_suffixes() {
+      let resolver = PluralResolver::default();
+      let first = resolver.get_suffixes("en").unwrap();
+      let second = resolver.get_suffixes("en").unwrap();
+      assert_eq!(first, vec!["_one", "_other"]);
+      assert_eq!(first, second);
+      assert_eq!(resolver.suffixes.borrow().len(), 1);
+
+      assert_eq!(resolver.get_suffixes("fr").unwrap(), vec!["_one", "_many", "_other"]);
+      assert_eq!(resolver.suffixes.borrow().len(), 2);
+    }
+
     #[test_log::test]
     fn get_rule_returns_none_for_non_existent_code() {
       let resolver = PluralResolver::default();
@@ -477,6 +562,55 @@ mod tests {
       assert!(suffixes.is_err());
     }
 
+    #[test_log::test]
+    fn get_suffixes_return_numeric_indices_for_ru_with_v3() {
+      let resolver = PluralResolver::new(false, Some("_".to_string()), I18NVersion::V3);
+      assert_eq!(resolver.get_suffixes("ru").unwrap(), vec!["_0", "_1", "_2"]);
+      assert_eq!(resolver.get_suffixes("en").unwrap(), vec!["_0", "_1"]);
+    }
+
+    #[test_log::test]
+    fn get_rule_handles_region_subtags() {
+      let resolver = PluralResolver::default();
+      let (numbers, plural_func) = resolver.get_rule("pt-BR").unwrap();
+      assert_eq!(numbers, &vec![1, 2]);
+      assert_eq!((plural_func(0), plural_func(1), plural_func(2)), (0, 0, 1));
+      assert_eq!(resolver.get_rule("pt_BR").map(|(numbers, _)| numbers), Some(&vec![1, 2]));
+
+      let (_, plural_func) = resolver.get_rule("pt-PT").unwrap();
+      assert_eq!((plural_func(0), plural_func(1), plural_func(2)), (1, 0, 1));
+
+      assert_eq!(resolver.get_rule("en-US").map(|(numbers, _)| numbers), Some(&vec![1, 2]));
+      assert_eq!(resolver.get_rule("zh-Hant").map(|(numbers, _)| numbers), Some(&vec![1]));
+    }
+
+    #[test_log::test]
+    fn get_suffixes_handles_region_subtags() {
+      let resolver = PluralResolver::default();
+      assert_eq!(resolver.get_suffixes("pt-BR").unwrap(), vec!["_one", "_many", "_other"]);
+      assert_eq!(resolver.get_suffixes("en-US").unwrap(), vec!["_one", "_other"]);
+      assert_eq!(resolver.get_suffixes("zh-Hant").unwrap(), vec!["_other"]);
+
+      let resolver = PluralResolver::new(false, Some("_".to_string()), I18NVersion::V3);
+      assert_eq!(resolver.get_suffixes("pt-BR").unwrap(), vec!["_0", "_1"]);
+      assert_eq!(resolver.get_suffixes("zh-Hant").unwrap(), vec!["_0"]);
+    }
+
+    #[test_log::test]
+    fn get_suffixes_uses_the_overridden_categories() {
+      let overrides =
+        HashMap::from([("en".to_string(), vec!["one".to_string(), "other".to_string(), "extra".to_string()])]);
+      let resolver = PluralResolver::default().with_overrides(overrides);
+      assert_eq!(resolver.get_suffixes("en").unwrap(), vec!["_one", "_other", "_extra"]);
+      assert_eq!(resolver.get_suffixes("fr").unwrap(), vec!["_one", "_many", "_other"]);
+      assert_eq!(resolver.get_suffixes("en_GB").unwrap(), vec!["_one", "_other", "_extra"]);
+
+      let overrides = HashMap::from([("pt_BR".to_string(), vec!["one".to_string(), "other".to_string()])]);
+      let resolver = PluralResolver::default().with_overrides(overrides);
+      assert_eq!(resolver.get_suffixes("pt-BR").unwrap(), vec!["_one", "_other"]);
+      assert_eq!(resolver.get_suffixes("pt").unwrap(), vec!["_one", "_many", "_other"]);
+    }
+
     #[test_log::test]
     fn get_suffix_returns_empty_string_for_non_existent_code() {
       let resolver = PluralResolver::default();
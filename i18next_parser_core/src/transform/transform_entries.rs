@@ -1,11 +1,16 @@
 //! This module contains the logic to transform entries into a JSON object.
 use std::collections::HashMap;
 
-use log::error;
+use color_eyre::eyre::bail;
+use log::{error, warn};
 use serde_json::Value;
 
 use crate::{
   config::Config,
+  helper::{
+    dot_path_to_hash::{split_unescaped, splits_key},
+    icu_message::is_icu_message,
+  },
   transform::{plural::PluralResolver, transform_entry::transform_entry},
   Entry,
 };
@@ -22,8 +27,88 @@ pub struct TransformEntriesResult {
   pub locale: String,
 }
 
+/// Checks that the nesting depth of the entry key does not exceed `config.max_key_depth`.
+///
+/// The check is only done for the source locale, so that the entry is reported once.
+///
+/// # Errors
+///
+/// Returns an error when the key is too deep and `config.fail_on_warnings` is set.
+fn check_key_depth(entry: &Entry, locale: &str, config: &Config) -> color_eyre::Result<()> {
+  let Some(max_key_depth) = config.max_key_depth else {
+    return Ok(());
+  };
+  if config.get_source_locale().is_some_and(|source| source != locale) {
+    return Ok(());
+  }
+  let depth = if splits_key(entry) { split_unescaped(&entry.key, &config.key_separator).len() } else { 1 };
+  if depth > max_key_depth {
+    warn!("Key {} has a nesting depth of {depth}, exceeding the maximum of {max_key_depth}", entry.key);
+    if config.fail_on_warnings {
+      bail!("Key {} has a nesting depth of {depth}, exceeding the maximum of {max_key_depth}", entry.key);
+    }
+  }
+  Ok(())
+}
+
+/// Checks that the default value of the entry is not its key, when `config.warn_on_key_equals_value` is set.
+///
+/// The check is only done for the source locale, so that the entry is reported once.
+///
+/// # Errors
+///
+/// Returns an error when the default value is the key and `config.fail_on_warnings` is set.
+fn check_key_equals_value(entry: &Entry, locale: &str, config: &Config) -> color_eyre::Result<()> {
+  if !config.warn_on_key_equals_value || config.get_source_locale().is_some_and(|source| source != locale) {
+    return Ok(());
+  }
+  if entry.value.as_ref().is_some_and(|value| value == &entry.key) {
+    warn!("Key {} has the same default value", entry.key);
+    if config.fail_on_warnings {
+      bail!("Key {} has the same default value", entry.key);
+    }
+  }
+  Ok(())
+}
+
+/// Returns the default value of the plural category at `idx` when the `defaultValue` of the entry is an array.
+///
+/// The categories beyond the end of the array take the last element of the array.
+fn plural_default_value(entry: &Entry, idx: usize) -> Option<Option<String>> {
+  let options = entry.i18next_options.as_ref()?;
+  let count = options.keys().filter(|key| key.starts_with("defaultValue[")).count();
+  if count == 0 {
+    return None;
+  }
+  options.get(&format!("defaultValue[{}]", idx.min(count - 1))).cloned()
+}
+
+/// Returns the default value of the base key of a context variant lacking its own, like i18next does.
+fn context_default_value<'a>(
+  entry: &Entry,
+  default_values: &HashMap<(&Option<String>, &String), &'a String>,
+  config: &Config,
+) -> Option<&'a String> {
+  if entry.value.as_ref().is_some_and(|value| !value.is_empty()) {
+    return None;
+  }
+  let context = entry.i18next_options.as_ref()?.get("context")?.as_ref()?;
+  let base_key = entry.key.strip_suffix(&format!("{}{context}", config.context_separator))?.to_string();
+  default_values.get(&(&entry.namespace, &base_key)).copied()
+}
+
+/// Checks if the default value of the entry is an ICU message, holding its own plural variants.
+fn has_icu_value(entry: &Entry) -> bool {
+  entry.value.as_ref().is_some_and(|value| is_icu_message(value))
+}
+
 /// Transforms entries into a JSON object.
 ///
+/// The entries restricted to another locale with the `lng` option are skipped, and the context variants without default
+/// value take the default value of their base key.
+/// The plural entries whose default value is an ICU message are written verbatim under their key, without plural
+/// suffixes.
+///
 /// # Arguments
 ///
 /// * `entries` - A reference to the entries to transform.
@@ -41,13 +126,43 @@ pub fn transform_entries(
   let mut unique_count = HashMap::new();
   let mut unique_plurals_count = HashMap::new();
 
-  let value = entries.iter().try_fold(Value::Object(Default::default()), |mut value, entry| {
-    if entry.has_count {
-      let suffixes = PluralResolver::default().get_suffixes(locale);
+  let plural_resolver =
+    PluralResolver::new(false, Some(config.plural_separator.clone()), (&config.plural_suffix_style).into())
+      .with_overrides(config.plural_overrides.clone());
+
+  let default_values = entries
+    .iter()
+    .filter_map(|entry| {
+      entry.value.as_ref().filter(|value| !value.is_empty()).map(|value| ((&entry.namespace, &entry.key), value))
+    })
+    .collect::<HashMap<_, _>>();
+
+  let mut entries = entries.iter().filter(|entry| entry.locale.as_ref().is_none_or(|lng| lng == locale));
+  let value = entries.try_fold(Value::Object(Default::default()), |mut value, entry| {
+    check_key_depth(entry, locale, config)?;
+    check_key_equals_value(entry, locale, config)?;
+    let context_entry;
+    let entry = match context_default_value(entry, &default_values, config) {
+      Some(default_value) => {
+        context_entry = entry.with_value(Some(default_value.clone()));
+        &context_entry
+      },
+      None => entry,
+    };
+    if entry.has_count && !has_icu_value(entry) {
+      let suffixes = plural_resolver.get_suffixes(locale);
       match suffixes {
         Ok(suffixes) => {
-          suffixes.iter().try_fold(value, |mut value, suffix| {
-            transform_entry(entry, &mut unique_count, &mut unique_plurals_count, &mut value, config, Some(suffix))
+          suffixes.iter().enumerate().try_fold(value, |mut value, (idx, suffix)| {
+            match plural_default_value(entry, idx) {
+              Some(default_value) => {
+                let entry = entry.with_value(default_value);
+                transform_entry(&entry, &mut unique_count, &mut unique_plurals_count, &mut value, config, Some(suffix))
+              },
+              None => {
+                transform_entry(entry, &mut unique_count, &mut unique_plurals_count, &mut value, config, Some(suffix))
+              },
+            }
           })
         },
         Err(e) => {
@@ -69,7 +184,7 @@ mod tests {
   use serde_json::json;
 
   use super::*;
-  use crate::Entry;
+  use crate::{config::PluralSuffixStyle, Entry};
 
   #[test]
   fn test_transform_entries() {
@@ -78,6 +193,9 @@ mod tests {
         namespace: Some("default".to_string()),
         key: "key1".to_string(),
         has_count: false,
+        count_var: None,
+        location: None,
+        locale: None,
         value: Some("value1".to_string()),
         i18next_options: None,
       },
@@ -85,6 +203,9 @@ mod tests {
         namespace: Some("default".to_string()),
         key: "key2".to_string(),
         has_count: true,
+        count_var: None,
+        location: None,
+        locale: None,
         value: Some("value2".to_string()),
         i18next_options: None,
       },
@@ -92,6 +213,9 @@ mod tests {
         namespace: Some("custom".to_string()),
         key: "key3".to_string(),
         has_count: false,
+        count_var: None,
+        location: None,
+        locale: None,
         value: Some("value3".to_string()),
         i18next_options: None,
       },
@@ -120,6 +244,9 @@ mod tests {
       namespace: Some("default".to_string()),
       key: "key".to_string(),
       has_count: true,
+      count_var: None,
+      location: None,
+      locale: None,
       value: Some("value".to_string()),
       i18next_options: None,
     }];
@@ -151,6 +278,9 @@ mod tests {
       namespace: Some("default".to_string()),
       key: "key".to_string(),
       has_count: true,
+      count_var: None,
+      location: None,
+      locale: None,
       value: Some("value".to_string()),
       i18next_options: None,
     }];
@@ -183,6 +313,9 @@ mod tests {
       namespace: Some("default".to_string()),
       key: "key".to_string(),
       has_count: true,
+      count_var: None,
+      location: None,
+      locale: None,
       value: Some("value".to_string()),
       i18next_options: None,
     }];
@@ -207,4 +340,106 @@ mod tests {
       })
     );
   }
+
+  #[test]
+  fn test_transform_entries_with_key_deeper_than_max_key_depth() {
+    let entries = vec![Entry::new_with_value("a.b.c.d", "value"), Entry::new_with_value("a.b", "value")];
+    let config = Config { max_key_depth: Some(3), ..Default::default() };
+
+    assert!(transform_entries(&entries, "en", &config).is_ok());
+
+    let config = Config { fail_on_warnings: true, ..config };
+    let result = transform_entries(&entries, "en", &config);
+    assert!(result.is_err());
+    assert!(check_key_depth(&entries[1], "en", &config).is_ok());
+
+    let config = Config { locales: vec!["en".into(), "fr".into()], ..config };
+    assert!(transform_entries(&entries, "fr", &config).is_ok(), "the key is only reported for the source locale");
+
+    let options = HashMap::from([("keySeparator".to_string(), Some("false".to_string()))]);
+    let entry = Entry { i18next_options: Some(options), ..Entry::new_with_value("a.b.c.d", "value") };
+    assert!(check_key_depth(&entry, "en", &config).is_ok());
+    assert!(check_key_depth(&Entry::new_with_value(r"a.b.c\.d", "value"), "en", &config).is_ok());
+  }
+
+  #[test]
+  fn test_transform_entries_with_numeric_plural_suffixes_ru() {
+    let entries = vec![Entry { has_count: true, ..Entry::new("key", "value", "default") }];
+    let config = Config { plural_suffix_style: PluralSuffixStyle::Numeric, ..Default::default() };
+
+    let result = transform_entries(&entries, "ru", &config).unwrap();
+
+    assert_eq!(result.unique_plurals_count.get("default"), Some(&3));
+    assert_eq!(result.value, json!({ "default": { "key_0": "value", "key_1": "value", "key_2": "value" } }));
+  }
+
+  #[test]
+  fn test_transform_entries_with_custom_plural_separator() {
+    let entries = vec![Entry { has_count: true, ..Entry::new("item", "value", "default") }];
+    let config = Config { plural_separator: "__".into(), ..Default::default() };
+
+    let result = transform_entries(&entries, "en", &config).unwrap();
+    assert_eq!(result.value, json!({ "default": { "item__one": "value", "item__other": "value" } }));
+
+    let config = Config { plural_suffix_style: PluralSuffixStyle::Numeric, ..config };
+    let result = transform_entries(&entries, "ru", &config).unwrap();
+    assert_eq!(result.value, json!({ "default": { "item__0": "value", "item__1": "value", "item__2": "value" } }));
+  }
+
+  #[test]
+  fn test_transform_entries_with_icu_default_value() {
+    let message = "{count, plural, one {# item} other {{count} items}}";
+    let entries = vec![Entry { has_count: true, ..Entry::new("key", message, "default") }];
+
+    let result = transform_entries(&entries, "en", &Default::default()).unwrap();
+
+    assert_eq!(result.unique_plurals_count.get("default"), Some(&0));
+    assert_eq!(result.value, json!({ "default": { "key": message } }));
+  }
+
+  #[test]
+  fn test_transform_entries_with_plural_default_values_en() {
+    let options =
+      [("defaultValue", "one item"), ("defaultValue[0]", "one item"), ("defaultValue[1]", "{{count}} items")]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), Some(value.to_string())))
+        .collect();
+    let entries =
+      vec![Entry { has_count: true, i18next_options: Some(options), ..Entry::new("key", "one item", "default") }];
+
+    let result = transform_entries(&entries, "en", &Default::default()).unwrap();
+
+    assert_eq!(result.value, json!({ "default": { "key_one": "one item", "key_other": "{{count}} items" } }));
+  }
+
+  #[test]
+  fn test_key_equals_value() {
+    let entries = vec![Entry::new_with_value("Submit", "Submit"), Entry::new_with_value("form.submit", "Submit")];
+    let config = Config {
+      locales: vec!["en".into(), "fr".into()],
+      warn_on_key_equals_value: true,
+      fail_on_warnings: true,
+      ..Default::default()
+    };
+
+    let error = transform_entries(&entries, "en", &config).err().unwrap();
+    assert_eq!(error.to_string(), "Key Submit has the same default value");
+    assert!(transform_entries(&entries, "fr", &config).is_ok(), "the key is only reported for the source locale");
+    assert!(transform_entries(&entries, "en", &Config { fail_on_warnings: false, ..config.clone() }).is_ok());
+    assert!(transform_entries(&entries, "en", &Config { warn_on_key_equals_value: false, ..config }).is_ok());
+  }
+
+  #[test]
+  fn test_transform_entries_with_context_inheriting_the_base_default_value() {
+    let options = [("context".to_string(), Some("male".to_string()))].into_iter().collect();
+    let entries = vec![Entry::new("friend", "A friend", "default"), Entry {
+      key: "friend_male".into(),
+      i18next_options: Some(options),
+      ..Entry::new_with_ns("", "default")
+    }];
+
+    let result = transform_entries(&entries, "en", &Default::default()).unwrap();
+
+    assert_eq!(result.value, json!({ "default": { "friend": "A friend", "friend_male": "A friend" } }));
+  }
 }
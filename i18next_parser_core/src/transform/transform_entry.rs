@@ -22,7 +22,7 @@ pub fn transform_entry(
   options: &Config,
   suffix: Option<&str>,
 ) -> color_eyre::Result<Value> {
-  let namespace = entry.namespace.clone().unwrap_or("default".to_string());
+  let namespace = entry.namespace.clone().unwrap_or(options.default_namespace.clone());
   if !unique_count.contains_key(&namespace) {
     unique_count.insert(namespace.clone(), 0);
   }
@@ -32,21 +32,28 @@ pub fn transform_entry(
 
   let result = dot_path_to_hash(entry, value, suffix, options);
 
+  let location = entry.location.as_ref().map(|location| format!(" at {location}")).unwrap_or_default();
   match result.conflict {
     Some(Conflict::Key(key)) => {
-      warn!("Found translation key already mapped to a map or parent of new key already mapped to a string: {key}");
+      warn!(
+        "Found translation key already mapped to a map or parent of new key already mapped to a string: {key}{location}"
+      );
       if options.fail_on_warnings {
-        bail!("Found translation key already mapped to a map or parent of new key already mapped to a string: {key}")
+        bail!(
+          "Found translation key already mapped to a map or parent of new key already mapped to a string: \
+           {key}{location}"
+        );
       }
     },
     Some(Conflict::Value(old, new)) => {
       let separator: &str = options.namespace_separator.as_ref();
       let diff = get_char_diff(&old, &new);
       warn!(
-        "Found same keys with different values: {namespace}{separator}{key}: {diff}",
+        "Found same keys with different values: {namespace}{separator}{key}: {diff}{location}",
         namespace = namespace.bright_yellow(),
         key = entry.key.blue(),
-        diff = diff
+        diff = diff,
+        location = location.yellow()
       );
     },
     _ => {
@@ -73,6 +80,9 @@ mod tests {
       key: "key1".to_string(),
       value: Some("value1".to_string()),
       has_count: false,
+      count_var: None,
+      location: None,
+      locale: None,
       i18next_options: None,
     };
     let mut unique_count = HashMap::new();
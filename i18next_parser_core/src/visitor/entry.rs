@@ -1,5 +1,35 @@
+use std::{fmt::Display, path::PathBuf};
+
+use oxc_span::Span;
+
 use crate::visitor::I18NextOptions;
 
+/// This struct represents the location of an entry in the source files.
+///
+/// # Fields
+///
+/// * `file_path` - The path of the file containing the entry.
+/// * `span` - The span of the `t()` call or `Trans` element in the parsed source.
+/// * `position` - The line and column (both starting at 1) of the entry in the file.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EntryLocation {
+  /// the path of the file containing the entry
+  pub file_path: PathBuf,
+  /// the span of the entry in the parsed source
+  pub span: Span,
+  /// the line and column of the entry in the file (if known)
+  pub position: Option<(usize, usize)>,
+}
+
+impl Display for EntryLocation {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self.position {
+      Some((line, column)) => write!(f, "{}:{line}:{column}", self.file_path.display()),
+      None => write!(f, "{}", self.file_path.display()),
+    }
+  }
+}
+
 /// This struct represents an entry in the i18n system.
 ///
 /// # Fields
@@ -9,6 +39,9 @@ use crate::visitor::I18NextOptions;
 /// * `namespace` - The namespace found for the key.
 /// * `i18next_options` - All i18next options found in the file.
 /// * `has_count` - A boolean indicating whether the key has a count (if plural).
+/// * `count_var` - The name of the variable passed as count, like `items.length`.
+/// * `location` - The location of the entry in the source files.
+/// * `locale` - The locale the entry is restricted to, when forced with the `lng` option.
 #[derive(Debug, Default, Eq)]
 #[allow(dead_code)]
 pub struct Entry {
@@ -22,6 +55,12 @@ pub struct Entry {
   pub i18next_options: Option<I18NextOptions>,
   /// the count found for the key (if plural)
   pub has_count: bool,
+  /// the name of the variable passed as count (if plural)
+  pub count_var: Option<String>,
+  /// the location of the entry in the source files
+  pub location: Option<EntryLocation>,
+  /// the locale the entry is restricted to (if forced with `lng`)
+  pub locale: Option<String>,
 }
 
 /// Implement the `PartialEq` trait for `Entry`.
@@ -51,4 +90,18 @@ impl Entry {
   pub fn new_with_ns<Key: Into<String>, Ns: Into<String>>(key: Key, namespace: Ns) -> Self {
     Self { key: key.into(), namespace: Some(namespace.into()), ..Default::default() }
   }
+
+  /// Create a copy of the entry with another value.
+  pub(crate) fn with_value(&self, value: Option<String>) -> Self {
+    Self {
+      key: self.key.clone(),
+      value,
+      namespace: self.namespace.clone(),
+      i18next_options: self.i18next_options.clone(),
+      has_count: self.has_count,
+      count_var: self.count_var.clone(),
+      location: self.location.clone(),
+      locale: self.locale.clone(),
+    }
+  }
 }
@@ -1,32 +1,58 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+  collections::HashMap,
+  path::{Path, PathBuf},
+  sync::{Arc, LazyLock, Mutex},
+  time::SystemTime,
+};
 
 use color_eyre::owo_colors::OwoColorize;
 use log::{debug, trace, warn};
-use oxc_ast::ast::{
-  Argument,
-  CallExpression,
-  Expression,
-  IdentifierReference,
-  JSXAttributeItem,
-  JSXAttributeName,
-  JSXAttributeValue,
-  JSXChild,
-  JSXElement,
-  JSXElementName,
-  JSXExpression,
-  ObjectExpression,
-  ObjectPropertyKind,
-  Program,
-  Statement,
+use oxc_allocator::Allocator;
+use oxc_ast::{
+  ast::{
+    Argument,
+    ArrayExpression,
+    BindingPatternKind,
+    CallExpression,
+    ConditionalExpression,
+    Declaration,
+    Expression,
+    IdentifierReference,
+    ImportDeclarationSpecifier,
+    JSXAttributeItem,
+    JSXAttributeName,
+    JSXAttributeValue,
+    JSXChild,
+    JSXElement,
+    JSXElementName,
+    JSXExpression,
+    ObjectExpression,
+    ObjectPropertyKind,
+    Program,
+    Statement,
+    StaticMemberExpression,
+    TSEnumDeclaration,
+    TSEnumMemberName,
+    TemplateLiteral,
+    VariableDeclaration,
+    VariableDeclarator,
+  },
+  Trivias,
 };
+use oxc_parser::Parser;
+use oxc_span::{SourceType, Span};
+use regex::Regex;
 use serde_json::Value;
 use tracing::span;
 
 use crate::{
   clean_multi_line_code,
+  config::{InterpolationEscape, OnUnresolved},
+  helper::{escape_interpolation::escape_interpolation, icu_message::is_icu_message, line_index::LineIndex},
   visitor::node_child::{NodeChild, NodeTag},
   Config,
   Entry,
+  EntryLocation,
   IsEmpty,
 };
 
@@ -35,22 +61,127 @@ use crate::{
 /// and the value is an Option<`String`> representing the option value.
 pub type I18NextOptions = HashMap<String, Option<String>>;
 
+/// The JSX components whose children are extracted as translations.
+pub(super) const TRANS_COMPONENTS: &[&str] = &["Trans"];
+
+/// The marker replacing the keys and namespaces that cannot be resolved when `on_unresolved` is `Placeholder`.
+pub const UNRESOLVED_PLACEHOLDER: &str = "__UNRESOLVED__";
+
+/// The extensions tried when resolving a module imported without extension.
+const MODULE_EXTENSIONS: &[&str] = &["ts", "tsx", "js", "jsx", "mjs", "cjs"];
+
+/// Matches the text of a `/** @default value */` comment, capturing the value.
+static DEFAULT_ANNOTATION_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\*\s*@default\s+(.+?)\s*$").unwrap());
+
+/// The constants declared in a module, by name.
+type ModuleConstants = Arc<HashMap<String, String>>;
+
+/// The constants of the modules parsed to resolve the imports, by path of the module, with the modification time of the
+/// module they were read from.
+static MODULE_CONSTANTS: LazyLock<Mutex<HashMap<PathBuf, (SystemTime, ModuleConstants)>>> =
+  LazyLock::new(Default::default);
+
+/// Resolve the path of an imported module, from the file importing it for a relative specifier, like `./ns`, and from
+/// the input roots otherwise, like `constants/ns`.
+fn resolve_module_path(file_path: &Path, specifier: &str, input_roots: &[PathBuf]) -> Option<PathBuf> {
+  let with_extension = |base: &Path, extension: &str| {
+    let mut path = base.as_os_str().to_os_string();
+    path.push(format!(".{extension}"));
+    PathBuf::from(path)
+  };
+  let candidates = |base: PathBuf| {
+    let index = base.join("index");
+    std::iter::once(base.clone())
+      .chain(MODULE_EXTENSIONS.iter().map(|extension| with_extension(&base, extension)))
+      .chain(MODULE_EXTENSIONS.iter().map(|extension| with_extension(&index, extension)))
+      .collect::<Vec<_>>()
+  };
+  let bases = if specifier.starts_with('.') {
+    vec![file_path.parent()?.join(specifier)]
+  } else {
+    input_roots.iter().map(|root| root.join(specifier)).collect()
+  };
+  bases.into_iter().flat_map(candidates).find(|path| path.is_file())
+}
+
+/// Get the directories the input globs of the configuration start from, like `src` for `src/**/*.ts`.
+fn input_roots(config: &Config) -> Vec<PathBuf> {
+  let mut roots = config
+    .input
+    .iter()
+    .map(|input| {
+      let root = Path::new(input)
+        .components()
+        .take_while(|component| !component.as_os_str().to_string_lossy().contains(['*', '?', '[', '{']))
+        .collect::<PathBuf>();
+      config.working_dir.join(root)
+    })
+    .collect::<Vec<_>>();
+  roots.sort();
+  roots.dedup();
+  roots
+}
+
+/// Get the variable declaration of a statement, exported or not.
+fn variable_declaration<'b, 'a>(stmt: &'b Statement<'a>) -> Option<&'b VariableDeclaration<'a>> {
+  match stmt {
+    Statement::VariableDeclaration(var) => Some(var),
+    Statement::ExportNamedDeclaration(export) => {
+      match &export.declaration {
+        Some(Declaration::VariableDeclaration(var)) => Some(var),
+        _ => None,
+      }
+    },
+    _ => None,
+  }
+}
+
 /// This struct represents the options for the I18NVisitor.
 ///
 /// # Fields
 ///
 /// * `namespace_separator` - The spearator to use for the namespace inside a key.
 /// * `trans_keep_basic_html_nodes_for` - An optional vector of strings representing the basic HTML nodes to be kept for translation.
-#[derive(Debug, Default)]
+/// * `key_separator` - The separator to use between a key prefix and a key.
+/// * `context_separator` - The separator to use between a key and its context.
+/// * `interpolation_escape` - The escaping applied to the interpolations of the extracted default values.
+/// * `namespace_from_filename` - Whether the file name is used as namespace of the keys without namespace.
+/// * `key_functions` - The functions, like `i18n.exists`, whose first argument is a key to extract without value.
+/// * `trans_key_from_children` - Whether the children of a `Trans` component without `i18nKey` are used as its key.
+/// * `resolve_imports` - Whether the constants imported from other modules are read from these modules.
+/// * `on_unresolved` - The handling of the keys and namespaces that cannot be resolved.
+/// * `input_roots` - The directories the modules imported with a non-relative specifier are resolved from.
+#[derive(Clone, Debug, Default)]
 pub struct VisitorOptions {
   pub namespace_separator: Option<String>,
+  pub key_separator: Option<String>,
+  pub context_separator: Option<String>,
   pub trans_keep_basic_html_nodes_for: Option<Vec<String>>,
+  pub interpolation_escape: InterpolationEscape,
+  pub namespace_from_filename: bool,
+  pub key_functions: Vec<String>,
+  pub trans_key_from_children: bool,
+  pub resolve_imports: bool,
+  pub on_unresolved: OnUnresolved,
+  pub input_roots: Vec<PathBuf>,
 }
 
 impl VisitorOptions {
   pub fn new<C: AsRef<Config>>(config: C) -> Self {
     let config = config.as_ref();
-    VisitorOptions { namespace_separator: Some(config.namespace_separator.clone()), ..Default::default() }
+    VisitorOptions {
+      namespace_separator: Some(config.namespace_separator.clone()),
+      key_separator: Some(config.key_separator.clone()),
+      context_separator: Some(config.context_separator.clone()),
+      interpolation_escape: config.interpolation_escape.clone(),
+      namespace_from_filename: config.namespace_from_filename,
+      key_functions: config.key_functions.clone(),
+      trans_key_from_children: config.trans_key_from_children,
+      resolve_imports: config.resolve_imports,
+      on_unresolved: config.on_unresolved.clone(),
+      input_roots: input_roots(config),
+      ..Default::default()
+    }
   }
 }
 
@@ -62,6 +193,10 @@ impl VisitorOptions {
 /// * `entries` - A vector of entries in the i18n system.
 /// * `options` - The options for the I18NVisitor.
 /// * `current_namespace` - The current namespace while parsing a file.
+/// * `current_key_prefix` - The current key prefix while parsing a file.
+/// * `t_aliases` - The names `t` is renamed to when destructured from `useTranslation`.
+/// * `default_annotations` - The default values of the `/** @default value */` comments, by end of the preceding code.
+/// * `line_index` - The index locating the entries in the file being parsed.
 #[derive(Debug)]
 pub struct I18NVisitor<'a> {
   /// the program to be parsed
@@ -74,6 +209,14 @@ pub struct I18NVisitor<'a> {
   pub options: VisitorOptions,
   /// the current namespace while parsing a file
   pub(super) current_namespace: Option<String>,
+  /// the current key prefix while parsing a file
+  pub(super) current_key_prefix: Option<String>,
+  /// the names `t` is renamed to while parsing a file
+  pub(super) t_aliases: Vec<String>,
+  /// the default values annotated in the comments of the file, by the position of the end of the preceding code
+  pub(super) default_annotations: HashMap<u32, String>,
+  /// the index mapping the spans of the program to the lines and columns of the file
+  pub(super) line_index: LineIndex<'a>,
 }
 
 /// The visitor implementation that will search for translations inside javascript code
@@ -86,9 +229,56 @@ impl<'a> I18NVisitor<'a> {
       entries: Default::default(),
       options: VisitorOptions::new(config),
       current_namespace: Default::default(),
+      current_key_prefix: Default::default(),
+      t_aliases: Default::default(),
+      default_annotations: Default::default(),
+      line_index: Default::default(),
     }
   }
 
+  /// Locate the entries in the file they come from with the given index.
+  pub(crate) fn with_line_index(mut self, line_index: LineIndex<'a>) -> Self {
+    self.line_index = line_index;
+    self
+  }
+
+  /// Read the `/** @default value */` annotations of the comments of the file, supplying the default value of the
+  /// call they follow on the same line.
+  ///
+  /// # Arguments
+  ///
+  /// * `source_text` - The source text of the program
+  /// * `trivias` - The trivias of the program, holding its comments
+  pub fn with_comments(mut self, source_text: &str, trivias: &Trivias) -> Self {
+    let is_blank = |c: char| c == ' ' || c == '\t';
+    self.default_annotations = trivias
+      .comments()
+      .filter(|comment| comment.kind.is_multi_line())
+      .filter_map(|comment| {
+        let text = comment.span.source_text(source_text);
+        let value = DEFAULT_ANNOTATION_REGEX.captures(text)?.get(1)?.as_str().to_string();
+        // the comment text starts after its `/*` delimiter, and only follows the code of its line
+        let preceding = source_text[..comment.span.start as usize - 2].trim_end_matches(is_blank);
+        let preceding = preceding.strip_suffix(';').unwrap_or(preceding).trim_end_matches(is_blank);
+        if preceding.is_empty() || preceding.ends_with(['\n', '\r']) {
+          return None;
+        }
+        Some((preceding.len() as u32, value))
+      })
+      .collect();
+    self
+  }
+
+  /// Get the default value annotated in a comment following an expression, if any.
+  pub(super) fn annotated_default_value(&self, span: Span) -> Option<String> {
+    self.default_annotations.get(&span.end).cloned()
+  }
+
+  /// Get the location in the file being parsed of a span of the program.
+  pub(super) fn entry_location(&self, span: Span) -> EntryLocation {
+    EntryLocation { file_path: self.file_path.clone(), span, position: self.line_index.position(span.start) }
+  }
+
   /// Parse an expression to find its value
   ///
   /// # Arguments
@@ -105,10 +295,14 @@ impl<'a> I18NVisitor<'a> {
     match expr {
       Expression::StringLiteral(str) => Some(json!(str.value.to_string())),
       Expression::NumericLiteral(num) => Some(json!(num.value.to_string())),
+      Expression::BigIntLiteral(num) => Some(json!(num.raw.trim_end_matches('n'))),
       Expression::BooleanLiteral(bool) => Some(json!(bool.value.to_string())),
       Expression::Identifier(identifier) => self.find_identifier_value(identifier),
       Expression::TSSatisfiesExpression(expr) => self.parse_expression(&expr.expression),
       Expression::TSAsExpression(expression) => self.parse_expression(&expression.expression),
+      Expression::TSNonNullExpression(expression) => self.parse_expression(&expression.expression),
+      Expression::TSInstantiationExpression(expression) => self.parse_expression(&expression.expression),
+      Expression::AwaitExpression(expression) => self.parse_expression(&expression.argument),
       _ => {
         debug!("Unsupported expression: {expr:?}");
         None
@@ -125,11 +319,11 @@ impl<'a> I18NVisitor<'a> {
   /// # Returns
   ///
   /// An optional value representing the value of the expression
-  fn parse_expression_as_string(&self, expr: &Expression<'_>) -> Option<String> {
+  pub(super) fn parse_expression_as_string(&self, expr: &Expression<'_>) -> Option<String> {
     trace!("Parsing expression: {:?}", expr.bright_black().italic());
 
     match expr {
-      Expression::StaticMemberExpression(expression) => self.parse_expression_as_string(&expression.object),
+      Expression::StaticMemberExpression(expression) => self.parse_member_expression_as_string(expression),
       Expression::Identifier(identifier) => {
         trace!("Looking for identifier value from expression");
         self.find_identifier_value_as_string(identifier)
@@ -139,9 +333,16 @@ impl<'a> I18NVisitor<'a> {
         self.parse_expression_as_string(&expr.expression)
       },
       Expression::StringLiteral(str) => Some(str.value.to_string()),
+      Expression::TemplateLiteral(template) if template.expressions.is_empty() => {
+        Some(Self::template_literal_to_string(template))
+      },
       Expression::NumericLiteral(num) => Some(num.value.to_string()),
+      Expression::BigIntLiteral(num) => Some(num.raw.trim_end_matches('n').to_string()),
       Expression::BooleanLiteral(bool) => Some(bool.value.to_string()),
       Expression::TSAsExpression(expression) => self.parse_expression_as_string(&expression.expression),
+      Expression::TSNonNullExpression(expression) => self.parse_expression_as_string(&expression.expression),
+      Expression::TSInstantiationExpression(expression) => self.parse_expression_as_string(&expression.expression),
+      Expression::AwaitExpression(expression) => self.parse_expression_as_string(&expression.argument),
       _ => {
         if cfg!(debug_assertions) {
           warn!("Unsupported expression (str): {expr:?}");
@@ -153,6 +354,72 @@ impl<'a> I18NVisitor<'a> {
     }
   }
 
+  /// Parse an expression holding a default value to find its value
+  ///
+  /// Unlike the keys and namespaces, the default values may be interpolated template literals, their `${name}`
+  /// being written as `{{name}}`.
+  ///
+  /// # Arguments
+  ///
+  /// * `expr` - The expression to parse
+  pub(super) fn parse_default_value_as_string(&self, expr: &Expression<'_>) -> Option<String> {
+    match expr {
+      Expression::TemplateLiteral(template) => Some(Self::template_literal_to_string(template)),
+      _ => self.parse_expression_as_string(expr),
+    }
+  }
+
+  /// Parse a conditional expression, like `cond ? 'A' : 'B'`, to find its value
+  ///
+  /// When the condition resolves to a boolean, the matching branch is used, otherwise the first branch that can be
+  /// resolved is used.
+  ///
+  /// # Arguments
+  ///
+  /// * `expr` - The conditional expression to parse
+  pub(super) fn parse_conditional_expression_as_string(&self, expr: &ConditionalExpression<'_>) -> Option<String> {
+    match self.parse_expression_as_string(&expr.test).as_deref() {
+      Some("true") => self.parse_expression_as_string(&expr.consequent),
+      Some("false") => self.parse_expression_as_string(&expr.alternate),
+      _ => {
+        self.parse_expression_as_string(&expr.consequent).or_else(|| self.parse_expression_as_string(&expr.alternate))
+      },
+    }
+  }
+
+  /// Convert a template literal to a string, replacing the js interpolations by i18next ones.
+  ///
+  /// # Arguments
+  ///
+  /// * `template` - The template literal to convert
+  ///
+  /// # Returns
+  ///
+  /// The template literal as a string, with `${name}` written as `{{name}}`
+  pub(super) fn template_literal_to_string(template: &TemplateLiteral<'_>) -> String {
+    fn interpolation_name(expr: &Expression<'_>) -> Option<String> {
+      match expr {
+        Expression::Identifier(identifier) => Some(identifier.name.to_string()),
+        Expression::StaticMemberExpression(member) => {
+          interpolation_name(&member.object).map(|object| format!("{}.{}", object, member.property.name))
+        },
+        _ => None,
+      }
+    }
+
+    let mut text = String::new();
+    for (idx, quasi) in template.quasis.iter().enumerate() {
+      text.push_str(quasi.value.cooked.as_ref().unwrap_or(&quasi.value.raw));
+      if let Some(expr) = template.expressions.get(idx) {
+        match interpolation_name(expr) {
+          Some(name) => text.push_str(&format!("{{{{{name}}}}}")),
+          None => warn!("Unsupported template literal expression: {expr:?}"),
+        }
+      }
+    }
+    text
+  }
+
   /// Find the value of an identifier.
   ///
   /// # Arguments
@@ -187,6 +454,29 @@ impl<'a> I18NVisitor<'a> {
     arr
   }
 
+  /// Find the expression used to initialize an identifier declared in the program
+  ///
+  /// # Arguments
+  ///
+  /// * `identifier` - The identifier to find the initializer of
+  ///
+  /// # Returns
+  ///
+  /// An optional expression initializing the identifier
+  fn find_identifier_init(&self, identifier: &IdentifierReference<'_>) -> Option<&'a Expression<'a>> {
+    self.program.body.iter().find_map(|stmt| {
+      if let Statement::VariableDeclaration(var) = stmt {
+        var
+          .declarations
+          .iter()
+          .find(|v| v.id.get_identifier() == Some(identifier.name.clone()))
+          .and_then(|item| item.init.as_ref())
+      } else {
+        None
+      }
+    })
+  }
+
   /// Find the value of an identifier as a string
   ///
   /// # Arguments
@@ -196,7 +486,10 @@ impl<'a> I18NVisitor<'a> {
   /// # Returns
   ///
   /// An optional string representing the value of the identifier
-  fn find_identifier_value_as_string(&self, identifier: &oxc_allocator::Box<IdentifierReference>) -> Option<String> {
+  pub(super) fn find_identifier_value_as_string(
+    &self,
+    identifier: &oxc_allocator::Box<IdentifierReference>,
+  ) -> Option<String> {
     let arr = self.program.body.iter().find_map(|stmt| {
       if let Statement::VariableDeclaration(var) = stmt {
         var
@@ -213,6 +506,7 @@ impl<'a> I18NVisitor<'a> {
       }
     });
 
+    let arr = arr.or_else(|| self.find_imported_value_as_string(&identifier.name));
     if arr.is_none() {
       debug!(
         "Cannot find identifier str value in {} for {name} {identifier:?}",
@@ -224,6 +518,190 @@ impl<'a> I18NVisitor<'a> {
     arr
   }
 
+  /// Find the values of the constants declared, exported or not, in the program as strings, by name
+  fn find_declared_values_as_string(&self) -> HashMap<String, String> {
+    let mut values = HashMap::new();
+    let declarators = self.program.body.iter().filter_map(variable_declaration).flat_map(|var| var.declarations.iter());
+    for declarator in declarators {
+      let Some(name) = declarator.id.get_identifier() else { continue };
+      let value = declarator.init.as_ref().and_then(|init| self.parse_expression_as_string(init));
+      if let Some(value) = value {
+        // the first declaration wins
+        values.entry(name.to_string()).or_insert(value);
+      }
+    }
+    values
+  }
+
+  /// Get the constants of a module, parsing it only when it has not been parsed since its last modification
+  ///
+  /// The module is parsed on its own, without following its own imports.
+  fn module_constants(&self, module_path: &Path) -> Option<ModuleConstants> {
+    let modified = std::fs::metadata(module_path).and_then(|metadata| metadata.modified()).ok()?;
+    if let Some((cached, constants)) = MODULE_CONSTANTS.lock().unwrap().get(module_path) {
+      if *cached == modified {
+        return Some(constants.clone());
+      }
+    }
+
+    trace!("Parsing the module {}", module_path.display().yellow());
+    let source_text = std::fs::read_to_string(module_path).ok()?;
+    let allocator = Allocator::default();
+    let source_type = SourceType::from_path(module_path).ok()?;
+    let parsed = Parser::new(&allocator, &source_text, source_type).parse();
+    let visitor = I18NVisitor {
+      program: &parsed.program,
+      file_path: module_path.to_path_buf(),
+      entries: Default::default(),
+      options: VisitorOptions { resolve_imports: false, ..self.options.clone() },
+      current_namespace: Default::default(),
+      current_key_prefix: Default::default(),
+      t_aliases: Default::default(),
+      default_annotations: Default::default(),
+      line_index: Default::default(),
+    };
+    let constants = Arc::new(visitor.find_declared_values_as_string());
+    MODULE_CONSTANTS.lock().unwrap().insert(module_path.to_path_buf(), (modified, constants.clone()));
+    Some(constants)
+  }
+
+  /// Find the value of a constant imported from another module as a string, when `resolve_imports` is enabled
+  ///
+  /// The constants of the imported module are parsed once and cached, without following its own imports.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The local name of the imported constant
+  ///
+  /// # Returns
+  ///
+  /// An optional string representing the value exported by the module
+  fn find_imported_value_as_string(&self, name: &str) -> Option<String> {
+    if !self.options.resolve_imports {
+      return None;
+    }
+    let (source, imported) = self.program.body.iter().find_map(|stmt| {
+      let Statement::ImportDeclaration(import) = stmt else { return None };
+      import.specifiers.as_ref()?.iter().find_map(|specifier| {
+        match specifier {
+          ImportDeclarationSpecifier::ImportSpecifier(specifier) if specifier.local.name == name => {
+            Some((import.source.value.to_string(), specifier.imported.name().to_string()))
+          },
+          _ => None,
+        }
+      })
+    })?;
+    let module_path = resolve_module_path(&self.file_path, &source, &self.options.input_roots)?;
+    trace!("Resolving {} from {}", imported.cyan(), module_path.display().yellow());
+    self.module_constants(&module_path)?.get(&imported).cloned()
+  }
+
+  /// Parse a member expression to find its value as a string
+  ///
+  /// A member of an enum declared in the program resolves to the value of the member, any other member expression
+  /// resolves to the value of its object.
+  ///
+  /// # Arguments
+  ///
+  /// * `member` - The member expression to parse
+  ///
+  /// # Returns
+  ///
+  /// An optional string representing the value of the member expression
+  pub(super) fn parse_member_expression_as_string(&self, member: &StaticMemberExpression<'_>) -> Option<String> {
+    if let Expression::Identifier(identifier) = &member.object {
+      if let Some(value) = self.find_enum_member_value(&identifier.name, &member.property.name) {
+        return Some(value);
+      }
+    }
+    self.parse_expression_as_string(&member.object)
+  }
+
+  /// Get the name of the variable used as an expression, like `count` or `items.length`
+  ///
+  /// # Arguments
+  ///
+  /// * `expr` - The expression to get the variable name of
+  fn expression_variable_name(expr: &Expression<'_>) -> Option<String> {
+    match expr {
+      Expression::Identifier(identifier) => Some(identifier.name.to_string()),
+      Expression::StaticMemberExpression(member) => {
+        Self::expression_variable_name(&member.object).map(|object| format!("{object}.{}", member.property.name))
+      },
+      Expression::TSAsExpression(expression) => Self::expression_variable_name(&expression.expression),
+      Expression::TSNonNullExpression(expression) => Self::expression_variable_name(&expression.expression),
+      _ => None,
+    }
+  }
+
+  /// Find the name of the variable passed as `count` in the options of a `t` call
+  ///
+  /// # Arguments
+  ///
+  /// * `arguments` - The arguments of the `t` call
+  pub(super) fn find_count_variable(&self, arguments: &[Argument<'_>]) -> Option<String> {
+    arguments.iter().skip(1).find_map(|arg| {
+      let Argument::ObjectExpression(obj) = arg else {
+        return None;
+      };
+      obj.properties.iter().find_map(|prop| {
+        match prop {
+          ObjectPropertyKind::ObjectProperty(kv) if kv.key.name().is_some_and(|name| name == "count") => {
+            Self::expression_variable_name(&kv.value)
+          },
+          _ => None,
+        }
+      })
+    })
+  }
+
+  /// Find the string value of an enum member declared in the program
+  ///
+  /// # Arguments
+  ///
+  /// * `enum_name` - The name of the enum
+  /// * `member_name` - The name of the member
+  ///
+  /// # Returns
+  ///
+  /// An optional string representing the value of the member
+  fn find_enum_member_value(&self, enum_name: &str, member_name: &str) -> Option<String> {
+    let find_member = |declaration: &TSEnumDeclaration<'_>| {
+      if declaration.id.name != enum_name {
+        return None;
+      }
+      declaration
+        .members
+        .iter()
+        .find(|member| {
+          match &member.id {
+            TSEnumMemberName::StaticIdentifier(identifier) => identifier.name == member_name,
+            TSEnumMemberName::StaticStringLiteral(str) => str.value == member_name,
+            _ => false,
+          }
+        })
+        .and_then(|member| {
+          match &member.initializer {
+            Some(Expression::StringLiteral(str)) => Some(str.value.to_string()),
+            _ => None,
+          }
+        })
+    };
+
+    self.program.body.iter().find_map(|stmt| {
+      match stmt {
+        Statement::TSEnumDeclaration(declaration) => find_member(declaration),
+        Statement::ExportNamedDeclaration(export) => {
+          match &export.declaration {
+            Some(Declaration::TSEnumDeclaration(declaration)) => find_member(declaration),
+            _ => None,
+          }
+        },
+        _ => None,
+      }
+    })
+  }
+
   /// Extract the namespace from the i18next function
   ///
   /// # Arguments
@@ -235,10 +713,10 @@ impl<'a> I18NVisitor<'a> {
   ///
   /// The namespace found in the function
   pub(super) fn extract_namespace(&mut self, name: &str, expr: &CallExpression<'a>) {
-    let arg = match name {
-      "useTranslation" | "withTranslation" => expr.arguments.first(),
-      "getFixedT" => expr.arguments.get(1),
-      _ => None,
+    let (arg, options) = match name {
+      "useTranslation" | "withTranslation" => (expr.arguments.first(), expr.arguments.get(1)),
+      "getFixedT" => (expr.arguments.get(1), expr.arguments.get(2)),
+      _ => return,
     };
     if let Some(arg) = arg {
       match arg {
@@ -250,17 +728,132 @@ impl<'a> I18NVisitor<'a> {
         Argument::Identifier(identifier) => {
           trace!("Looking for namespace {} value from identifier", name.cyan());
           let identifier = self.find_identifier_value_as_string(identifier);
-          self.current_namespace = identifier;
+          self.current_namespace = identifier.or_else(|| self.unresolved_placeholder());
         },
         Argument::TSAsExpression(expression) => {
           trace!("Looking for namespace {} value from `As` expression", name.cyan());
           self.current_namespace = self.parse_expression_as_string(&expression.expression);
         },
+        Argument::TSSatisfiesExpression(expression) => {
+          trace!("Looking for namespace {} value from `Satisfies` expression", name.cyan());
+          self.current_namespace = self.parse_expression_as_string(&expression.expression);
+        },
+        Argument::ObjectExpression(obj) => {
+          trace!("Looking for namespace {} value from options", name.cyan());
+          self.current_namespace = self.find_property_value_as_string(obj, "ns");
+          self.current_key_prefix = self.find_property_value_as_string(obj, "keyPrefix");
+          return;
+        },
         _ => {
           warn!("Unsupported argument for {name} {arg:?}");
         },
       }
     }
+    self.current_key_prefix = match options {
+      Some(Argument::ObjectExpression(obj)) => self.find_property_value_as_string(obj, "keyPrefix"),
+      Some(Argument::StringLiteral(str)) if !str.value.is_empty() => Some(str.value.to_string()),
+      _ => None,
+    };
+  }
+
+  /// Extract the namespace of a `t` function chained to the i18next function, like `useTranslation('ns').t('key')`
+  ///
+  /// # Arguments
+  ///
+  /// * `expr` - The call expression of the chained `t` function
+  pub(super) fn extract_chained_namespace(&mut self, expr: &CallExpression<'a>) {
+    let Expression::StaticMemberExpression(member) = &expr.callee else { return };
+    let Expression::CallExpression(call) = &member.object else { return };
+    if let Some(name) = call.callee_name() {
+      self.extract_namespace(name, call);
+    }
+  }
+
+  /// Check if a call is a call to one of the key functions, matched on its name or its `object.name` member path.
+  pub(super) fn is_key_function(&self, expr: &CallExpression<'a>) -> bool {
+    let callee = match &expr.callee {
+      Expression::Identifier(identifier) => identifier.name.to_string(),
+      Expression::StaticMemberExpression(member) => {
+        let Expression::Identifier(object) = &member.object else { return false };
+        format!("{}.{}", object.name, member.property.name)
+      },
+      _ => return false,
+    };
+    self.options.key_functions.iter().any(|function| function == &callee)
+  }
+
+  /// Check if a function name refers to the `t` function, either directly or through a renamed destructuring.
+  pub(super) fn is_t_function(&self, name: &str) -> bool {
+    name == "t" || self.t_aliases.iter().any(|alias| alias == name)
+  }
+
+  /// Track the renaming of `t` in a destructuring of the result of `useTranslation` or `withTranslation`
+  ///
+  /// # Arguments
+  ///
+  /// * `declarator` - The variable declarator, like `const { t: translate } = useTranslation()`
+  pub(super) fn extract_t_alias(&mut self, declarator: &VariableDeclarator<'a>) {
+    let Some(Expression::CallExpression(call)) = &declarator.init else { return };
+    if !matches!(call.callee_name(), Some("useTranslation" | "withTranslation")) {
+      return;
+    }
+    let BindingPatternKind::ObjectPattern(pattern) = &declarator.id.kind else { return };
+    for property in pattern.properties.iter() {
+      if property.key.static_name().as_deref() != Some("t") {
+        continue;
+      }
+      if let Some(alias) = property.value.get_identifier().filter(|alias| alias != "t") {
+        trace!("Found alias {} of t", alias.cyan());
+        self.t_aliases.push(alias.to_string());
+      }
+    }
+  }
+
+  /// Find the value of a property of an object as a string.
+  ///
+  /// # Arguments
+  ///
+  /// * `obj` - The object to find the property in
+  /// * `name` - The name of the property
+  fn find_property_value_as_string(&self, obj: &ObjectExpression<'_>, name: &str) -> Option<String> {
+    obj.properties.iter().find_map(|prop| {
+      match prop {
+        ObjectPropertyKind::ObjectProperty(kv) if kv.key.name().is_some_and(|key| key == name) => {
+          self.parse_expression_as_string(&kv.value)
+        },
+        _ => None,
+      }
+    })
+  }
+
+  /// Prefix the key with the current key prefix, if any.
+  pub(super) fn apply_key_prefix(&self, key: String) -> String {
+    match &self.current_key_prefix {
+      Some(prefix) => {
+        let separator = self.options.key_separator.as_deref().unwrap_or(".");
+        format!("{prefix}{separator}{key}")
+      },
+      None => key,
+    }
+  }
+
+  /// Suffix the key with the context found in the options, if any.
+  ///
+  /// The `defaultValue_<context>` option takes precedence over the default value of the key.
+  pub(super) fn apply_context(
+    &self,
+    key: String,
+    value: Option<String>,
+    options: Option<&I18NextOptions>,
+  ) -> (String, Option<String>) {
+    match options.and_then(|o| o.get("context").cloned().flatten()) {
+      Some(context) if !context.is_empty() => {
+        let separator = self.options.context_separator.as_deref().unwrap_or("_");
+        let value = options.and_then(|o| o.get(&format!("defaultValue_{context}")).cloned().flatten()).or(value);
+        (format!("{key}{separator}{context}"), value)
+      },
+      _ => (key, value),
+    }
   }
 
   /// Parse the i18next options
@@ -268,11 +861,12 @@ impl<'a> I18NVisitor<'a> {
   /// # Arguments
   ///
   /// * `obj` - The object expression to parse
+  /// * `spreads` - The identifiers of the objects being spread into `obj`, so that a cycle is spread only once
   ///
   /// # Returns
   ///
   /// The i18next options found in the object
-  fn parse_i18next_option(&self, obj: &oxc_allocator::Box<ObjectExpression>) -> I18NextOptions {
+  fn parse_i18next_option(&self, obj: &oxc_allocator::Box<ObjectExpression>, spreads: &[&str]) -> I18NextOptions {
     use color_eyre::owo_colors::OwoColorize;
 
     let len = obj.properties.len();
@@ -282,7 +876,7 @@ impl<'a> I18NVisitor<'a> {
       .properties
       .iter()
       .enumerate()
-      .filter_map(|(idx, prop)| {
+      .flat_map(|(idx, prop)| {
         match prop {
           ObjectPropertyKind::ObjectProperty(kv) => {
             let name = kv.key.name().unwrap();
@@ -294,7 +888,11 @@ impl<'a> I18NVisitor<'a> {
                 idx = idx.cyan(),
                 path = self.file_path.display().yellow()
               );
-              let value = self.parse_expression_as_string(&kv.value);
+              let value = if name.starts_with("defaultValue") {
+                self.parse_default_value_as_string(&kv.value)
+              } else {
+                self.parse_expression_as_string(&kv.value)
+              };
               trace!(
                 "Parsed {key}: {parsed_value:?} <- {value:?}",
                 key = name.blue(),
@@ -304,6 +902,10 @@ impl<'a> I18NVisitor<'a> {
               value
             };
 
+            if let ("defaultValue", Expression::ArrayExpression(array)) = (name.to_string().as_str(), &kv.value) {
+              return self.parse_plural_default_values(array);
+            }
+
             match name.to_string().as_str() {
               "defaultValue" | "count" | "namespace" => {
                 let value = parse();
@@ -313,23 +915,65 @@ impl<'a> I18NVisitor<'a> {
                 let value = parse();
                 Some(("namespace".into(), value))
               },
+              "context" | "lng" | "nsSeparator" | "keySeparator" => Some((name.to_string(), parse())),
+              name if name.starts_with("defaultValue_") => Some((name.to_string(), parse())),
               _ => {
                 debug!("Couldn't parse {}", name.yellow());
                 None
               },
             }
+            .into_iter()
+            .collect::<Vec<_>>()
           },
-          ObjectPropertyKind::SpreadProperty(_) => {
-            if cfg!(debug_assertions) {
-              warn!("Unsupported spread property in {file}", file = self.file_path.display().yellow());
+          ObjectPropertyKind::SpreadProperty(spread) => {
+            // the properties of a spread object declared in the file are read as if they were written inline
+            let spread_obj = match &spread.argument {
+              Expression::Identifier(identifier) if spreads.contains(&identifier.name.as_str()) => {
+                debug!("Skipping the cyclic spread of {}", identifier.name.yellow());
+                return vec![];
+              },
+              Expression::Identifier(identifier) => {
+                match self.find_identifier_init(identifier) {
+                  Some(Expression::ObjectExpression(obj)) => Some((obj, Some(identifier.name.as_str()))),
+                  _ => None,
+                }
+              },
+              Expression::ObjectExpression(obj) => Some((obj, None)),
+              _ => None,
+            };
+            match spread_obj {
+              Some((obj, name)) => {
+                let spreads = [spreads, name.as_slice()].concat();
+                self.parse_i18next_option(obj, &spreads).into_iter().collect()
+              },
+              None => {
+                if cfg!(debug_assertions) {
+                  warn!("Unsupported spread property in {file}", file = self.file_path.display().yellow());
+                }
+                vec![]
+              },
             }
-            None
           },
         }
       })
       .collect::<I18NextOptions>()
   }
 
+  /// Parse an array `defaultValue`, holding the default value of each plural category of the language in order.
+  ///
+  /// Each element is stored as a `defaultValue[<index>]` option, and the first one is used as the `defaultValue`.
+  fn parse_plural_default_values(&self, array: &ArrayExpression<'_>) -> Vec<(String, Option<String>)> {
+    let values = array
+      .elements
+      .iter()
+      .map(|element| element.as_expression().and_then(|expr| self.parse_default_value_as_string(expr)))
+      .collect::<Vec<_>>();
+    let default_value = values.first().cloned().flatten();
+    std::iter::once(("defaultValue".to_string(), default_value))
+      .chain(values.into_iter().enumerate().map(|(idx, value)| (format!("defaultValue[{idx}]"), value)))
+      .collect()
+  }
+
   /// Check if a prop exists in a JSX element
   ///
   /// # Arguments
@@ -405,6 +1049,18 @@ impl<'a> I18NVisitor<'a> {
                         JSXExpression::StaticMemberExpression(expression) => {
                           self.parse_expression_as_string(&expression.object)
                         },
+                        JSXExpression::ConditionalExpression(expression) => {
+                          trace!("Looking for conditional value for prop");
+                          self.parse_conditional_expression_as_string(expression)
+                        },
+                        JSXExpression::ArrayExpression(array) => {
+                          trace!("Looking for the first value of the array for prop");
+                          array
+                            .elements
+                            .first()
+                            .and_then(|element| element.as_expression())
+                            .and_then(|expr| self.parse_expression_as_string(expr))
+                        },
                         _ => todo!("expression container {e:?} not supported"),
                       }
                     },
@@ -428,6 +1084,30 @@ impl<'a> I18NVisitor<'a> {
       .map(|v| v.to_string())
   }
 
+  /// Get the name of the variable passed to a prop, like `count={items.length}`
+  ///
+  /// # Arguments
+  ///
+  /// * `elem` - The element to get the prop from
+  /// * `attribute_name` - The name of the prop
+  pub(super) fn get_prop_variable_name(&self, elem: &JSXElement<'_>, attribute_name: &str) -> Option<String> {
+    elem.opening_element.attributes.iter().find_map(|attribute| {
+      match attribute {
+        JSXAttributeItem::Attribute(attribute) => {
+          match (&attribute.name, &attribute.value) {
+            (JSXAttributeName::Identifier(identifier), Some(JSXAttributeValue::ExpressionContainer(container)))
+              if identifier.name == attribute_name =>
+            {
+              container.expression.as_expression().and_then(Self::expression_variable_name)
+            },
+            _ => None,
+          }
+        },
+        JSXAttributeItem::SpreadAttribute(_) => None,
+      }
+    })
+  }
+
   /// Convert the children of a tag to a string
   pub(super) fn elem_to_string(&self, childs: &[NodeChild]) -> String {
     childs
@@ -468,10 +1148,10 @@ impl<'a> I18NVisitor<'a> {
           },
           JSXChild::Element(element) => {
             let name = if let JSXElementName::Identifier(id) = &element.opening_element.name { &id.name } else { "" };
-            let is_basic = element.opening_element.attributes.len() == 0;
-            let has_dynamic_children = element.children.iter().any(|child| {
-              if let JSXChild::Element(e) = child {
-                if let JSXElementName::Identifier(id) = &e.opening_element.name {
+            let is_basic = element.opening_element.attributes.is_empty();
+            let has_dynamic_children = element.opening_element.attributes.iter().any(|attribute| {
+              if let JSXAttributeItem::Attribute(attribute) = attribute {
+                if let JSXAttributeName::Identifier(id) = &attribute.name {
                   id.name.eq("i18nIsDynamicList")
                 } else {
                   false
@@ -480,8 +1160,10 @@ impl<'a> I18NVisitor<'a> {
                 false
               }
             });
-            let children = if has_dynamic_children {
-              None
+            // dynamic lists are kept as an empty indexed tag so that the following children keep their index,
+            // nested translation components are extracted with their own key
+            let children = if has_dynamic_children || TRANS_COMPONENTS.contains(&name) {
+              Some(vec![])
             } else {
               let childs = &element.children;
               Some(Self::parse_children(childs))
@@ -498,7 +1180,10 @@ impl<'a> I18NVisitor<'a> {
             let exp = exp.expression.as_expression().map(Self::parse_expression_child);
             exp.unwrap_or(NodeChild::Text("".to_string()))
           },
-          _ => todo!(),
+          child => {
+            warn!("Unsupported JSX child: {child:?}");
+            NodeChild::Text("".to_string())
+          },
         }
       })
       .filter(|e| !e.is_empty())
@@ -510,7 +1195,7 @@ impl<'a> I18NVisitor<'a> {
       Expression::StringLiteral(str) => NodeChild::Text(str.value.to_string()),
       Expression::AssignmentExpression(e) => Self::parse_expression_child(&e.right),
       Expression::TSAsExpression(e) => Self::parse_expression_child(&e.expression),
-      Expression::CallExpression(e) if e.callee.is_identifier_reference() && e.arguments.len() >= 1 => {
+      Expression::CallExpression(e) if e.callee.is_identifier_reference() && !e.arguments.is_empty() => {
         Self::parse_expression_child(&e.callee)
       },
       Expression::ObjectExpression(e) => {
@@ -582,6 +1267,19 @@ impl<'a> I18NVisitor<'a> {
         trace!("translation value defined as string literal: {}", value.cyan());
         (Some(value), None)
       },
+      (Some(Argument::TemplateLiteral(template)), Some(Argument::ObjectExpression(obj))) => {
+        let value = Self::template_literal_to_string(template);
+        trace!("translation value defined as template literal: {}", value.cyan());
+        let (i18next_options, default_value) = self.parse_option_and_default_value(obj);
+
+        let value = if value.is_empty() { default_value } else { Some(value) };
+        (value, Some(i18next_options))
+      },
+      (Some(Argument::TemplateLiteral(template)), None) => {
+        let value = Self::template_literal_to_string(template);
+        trace!("translation value defined as template literal: {}", value.cyan());
+        (Some(value), None)
+      },
       (Some(Argument::ObjectExpression(obj)), None) => {
         trace!("settings provided as 2nd argument {:?}", obj.bright_black().italic());
         let (i18next_options, default_value) = self.parse_option_and_default_value(obj);
@@ -604,6 +1302,17 @@ impl<'a> I18NVisitor<'a> {
           todo!("Handle identifier {identifier:?}")
         }
       },
+      (Some(Argument::AwaitExpression(expression)), options) => {
+        trace!("translation value defined as awaited expression {:?}", expression.bright_black().italic());
+        let value = self.parse_expression_as_string(&expression.argument);
+        match options {
+          Some(Argument::ObjectExpression(obj)) => {
+            let (i18next_options, default_value) = self.parse_option_and_default_value(obj);
+            (value.or(default_value), Some(i18next_options))
+          },
+          _ => (value, None),
+        }
+      },
       (None, None) => (None, None),
       (arg_1, arg_2) => {
         warn!("Unknown argument combination type: {arg_1:?} {arg_2:?}");
@@ -612,19 +1321,14 @@ impl<'a> I18NVisitor<'a> {
     }
   }
 
-  /// Get the namespace for an entry
+  /// Split the namespace prefixing a key with the namespace separator, like `ns:key`
   ///
   /// # Arguments
   ///
-  /// * `key` - The key to get the namespace for
-  /// * `options` - The options to get the namespace from
-  pub(super) fn get_namespace(&self, options: Option<&I18NextOptions>, key: &str) -> (String, Option<String>) {
+  /// * `key` - The key to split
+  pub(super) fn split_namespace(&self, key: &str) -> (String, Option<String>) {
     let separator = self.options.namespace_separator.as_deref().unwrap_or(":");
     trace!("Namespace separator: {separator:?}", separator = separator.italic().cyan());
-    let current_namespace = &self.current_namespace;
-    trace!("Current namespace: {namespace:?}", namespace = current_namespace.italic().cyan());
-    let ns_from_options = options.and_then(|o| o.get("namespace").cloned().flatten());
-    trace!("Namespace from options: {namespace:?}", namespace = ns_from_options.italic().cyan());
 
     let (key, ns_from_key) = if key.contains(separator) {
       let mut split = key.split(separator);
@@ -635,17 +1339,86 @@ impl<'a> I18NVisitor<'a> {
       (key.to_string(), None)
     };
     trace!("Namespace from key: {namespace:?}", namespace = ns_from_key.italic().cyan());
+    (key, ns_from_key)
+  }
+
+  /// Get the namespace for an entry
+  ///
+  /// # Arguments
+  ///
+  /// * `key` - The key to get the namespace for
+  /// * `options` - The options to get the namespace from
+  pub(super) fn get_namespace(&self, options: Option<&I18NextOptions>, key: &str) -> (String, Option<String>) {
+    let current_namespace = &self.current_namespace;
+    trace!("Current namespace: {namespace:?}", namespace = current_namespace.italic().cyan());
+    let ns_from_options = match options.and_then(|o| o.get("namespace")) {
+      Some(None) => self.unresolved_placeholder(),
+      namespace => namespace.cloned().flatten(),
+    };
+    trace!("Namespace from options: {namespace:?}", namespace = ns_from_options.italic().cyan());
+
+    // a `nsSeparator: false` option of the call keeps the namespace separator in the key
+    let split_namespace =
+      options.and_then(|o| o.get("nsSeparator").cloned().flatten()).is_none_or(|ns_separator| ns_separator != "false");
+    let (key, ns_from_key) = if split_namespace { self.split_namespace(key) } else { (key.to_string(), None) };
 
     let namespace = ns_from_key.or(ns_from_options).or(current_namespace.clone());
     trace!("Namespace: {namespace:?}", namespace = namespace.italic().cyan());
     (key, namespace)
   }
 
-  fn parse_option_and_default_value(
+  /// Get the placeholder of a key or namespace that cannot be resolved, when `on_unresolved` is `Placeholder`
+  pub(super) fn unresolved_placeholder(&self) -> Option<String> {
+    match self.options.on_unresolved {
+      OnUnresolved::Skip => None,
+      OnUnresolved::Placeholder => {
+        warn!(
+          "Unresolved value replaced by {} in {}",
+          UNRESOLVED_PLACEHOLDER.yellow(),
+          self.file_path.display().yellow()
+        );
+        Some(UNRESOLVED_PLACEHOLDER.to_string())
+      },
+    }
+  }
+
+  /// Use the file name as namespace when none has been found and `namespace_from_filename` is enabled
+  pub(super) fn infer_namespace(&self, namespace: Option<String>) -> Option<String> {
+    if namespace.is_some() || !self.options.namespace_from_filename {
+      return namespace;
+    }
+    self.file_path.file_stem().and_then(|stem| stem.to_str()).map(|stem| stem.to_string())
+  }
+
+  /// Record an extracted entry, emitting it on the `entry` target so that the extraction can be audited
+  pub(super) fn push_entry(&mut self, entry: Entry) {
+    let key = match &entry.namespace {
+      Some(namespace) => {
+        let separator = self.options.namespace_separator.as_deref().unwrap_or(":");
+        format!("{namespace}{separator}{key}", key = entry.key)
+      },
+      None => entry.key.clone(),
+    };
+    tracing::trace!(target: "entry", "{key} in {file}", file = self.file_path.display());
+    self.entries.push(entry);
+  }
+
+  /// Apply the configured interpolation escaping to a default value, the ICU messages being kept as written
+  pub(super) fn escape_default_value(&self, value: Option<String>) -> Option<String> {
+    value.map(|value| {
+      if is_icu_message(&value) {
+        value
+      } else {
+        escape_interpolation(&value, &self.options.interpolation_escape)
+      }
+    })
+  }
+
+  fn parse_option_and_default_value(
     &mut self,
     obj: &oxc_allocator::Box<'_, ObjectExpression<'_>>,
   ) -> (I18NextOptions, Option<String>) {
-    let i18next_options = self.parse_i18next_option(obj);
+    let i18next_options = self.parse_i18next_option(obj, &[]);
     let default_value = i18next_options.get("defaultValue").cloned().flatten();
     if let Some(value) = i18next_options.get("defaultValue") {
       trace!("translation value found in i18next options: {value:?}");
@@ -675,6 +1448,18 @@ mod tests {
     visitor.entries
   }
 
+  fn parse_with_config(source_text: &str, config: Config) -> Vec<Entry> {
+    let allocator = Allocator::default();
+    let source_type = SourceType::from_path("file.tsx").unwrap();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+
+    let program = ret.program;
+
+    let mut visitor = I18NVisitor::new(&program, "file.tsx", config);
+    visitor.visit_program(&program);
+    visitor.entries
+  }
+
   fn parse_with_options(source_text: &str) -> Vec<Entry> {
     let allocator = Allocator::default();
     let source_type = SourceType::from_path("file.tsx").unwrap();
@@ -692,6 +1477,48 @@ mod tests {
   mod t_function {
     use super::*;
 
+    #[test_log::test]
+    fn should_infer_namespace_from_filename() {
+      let allocator = Allocator::default();
+      // language=javascript
+      let source_text = "const title = t('title'); const other = t('other', { ns: 'common' });";
+      let ret = Parser::new(&allocator, source_text, SourceType::from_path("login.tsx").unwrap()).parse();
+      let config = Config { namespace_from_filename: true, ..Default::default() };
+
+      let mut visitor = I18NVisitor::new(&ret.program, "src/login.tsx", config);
+      visitor.visit_program(&ret.program);
+
+      assert_eq!(visitor.entries, vec![Entry::new_with_ns("title", "login"), Entry::new_with_ns("other", "common")]);
+    }
+
+    #[test_log::test]
+    fn should_read_the_default_value_from_an_annotation() {
+      let allocator = Allocator::default();
+      // language=javascript
+      let source_text = r#"
+        const title = t('title') /** @default Hello */;
+        const body = t('body'); /** @default Body */
+        const other = t('other', 'Other') /** @default Ignored */;
+        const plain = t('plain') /* @default Ignored */;
+        const next = t('next');
+        /** @default 10 */
+        const count = 5;
+      "#;
+      let ret = Parser::new(&allocator, source_text, SourceType::from_path("file.tsx").unwrap()).parse();
+
+      let mut visitor =
+        I18NVisitor::new(&ret.program, "file.tsx", Config::default()).with_comments(source_text, &ret.trivias);
+      visitor.visit_program(&ret.program);
+
+      assert_eq!(visitor.entries, vec![
+        Entry::new_with_value("title", "Hello"),
+        Entry::new_with_value("body", "Body"),
+        Entry::new_with_value("other", "Other"),
+        Entry::empty("plain"),
+        Entry::empty("next"),
+      ]);
+    }
+
     #[test_log::test]
     fn should_parse_t_with_options_and_ns_defined_in_variable() {
       // language=javascript
@@ -702,6 +1529,137 @@ mod tests {
       assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
     }
 
+    #[test_log::test]
+    fn should_parse_t_in_jsx_attribute() {
+      // language=javascript
+      let source_text = "const Button = () => <button aria-label={t('a.label')}>{t('a.text')}</button>;";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::empty("a.label"), Entry::empty("a.text")]);
+
+      // language=javascript
+      let source_text =
+        "const { t } = useTranslation('ns'); const Button = () => <button aria-label={t('a.label')} />;";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("a.label", "ns")]);
+
+      // language=javascript
+      let source_text = "<Trans i18nKey='a.trans' title={t('a.title')} />";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::empty("a.trans"), Entry::empty("a.title")]);
+    }
+
+    #[test_log::test]
+    fn should_parse_t_chained_to_use_translation() {
+      // language=javascript
+      let source_text = "const title = useTranslation('ns').t('toast.title');";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
+
+      // language=javascript
+      let source_text = "const title = useTranslation('ns', { keyPrefix: 'toast' }).t('title');";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
+    }
+
+    #[test_log::test]
+    fn should_keep_the_namespace_in_nested_blocks() {
+      // language=javascript
+      let source_text = r#"
+        function Title({ kind }) {
+          const { t } = useTranslation('ns');
+          if (kind) {
+            return t('title.kind');
+          }
+          switch (kind) {
+            case 'a': {
+              for (const item of items) {
+                t('title.item');
+              }
+              return t('title.a');
+            }
+            default:
+              return t('title.default');
+          }
+        }
+      "#;
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![
+        Entry::new_with_ns("title.kind", "ns"),
+        Entry::new_with_ns("title.item", "ns"),
+        Entry::new_with_ns("title.a", "ns"),
+        Entry::new_with_ns("title.default", "ns"),
+      ]);
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_namespace_from_asserted_constant() {
+      // language=typescript
+      let source_text = "const ns = 'ns' as const; const { t } = useTranslation(ns); const title = t('toast.title');";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
+
+      // language=typescript
+      let source_text =
+        "const ns = 'ns' satisfies Ns; const { t } = useTranslation(ns as Ns); const title = t('toast.title');";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
+
+      // language=typescript
+      let source_text = "const { t } = useTranslation('ns' satisfies Ns); const title = t('toast.title');";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
+    }
+
+    #[test_log::test]
+    fn should_use_placeholder_for_unresolved_namespace() {
+      // language=javascript
+      let source_text = "const title = t('toast.title', { ns: getNamespace() });";
+      assert_eq!(parse(source_text), vec![Entry::empty("toast.title")]);
+
+      let config = Config { on_unresolved: OnUnresolved::Placeholder, ..Default::default() };
+      let keys = parse_with_config(source_text, config);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", UNRESOLVED_PLACEHOLDER)]);
+    }
+
+    #[test_log::test]
+    fn should_use_placeholder_for_unresolved_key() {
+      // language=javascript
+      let source_text = "const key = 'ns:toast.title'; const title = t(key);";
+      assert_eq!(parse(source_text), vec![Entry::new_with_ns("toast.title", "ns")]);
+
+      // language=javascript
+      let source_text = "const title = t(key);";
+      assert_eq!(parse(source_text), vec![]);
+
+      let config = Config { on_unresolved: OnUnresolved::Placeholder, ..Default::default() };
+      let keys = parse_with_config(source_text, config);
+      assert_eq!(keys, vec![Entry::empty(UNRESOLVED_PLACEHOLDER)]);
+    }
+
+    #[test_log::test]
+    fn should_use_placeholder_for_every_unresolved_key_form() {
+      let sources = [
+        "const title = t(key!);",
+        "const title = t(keys.title);",
+        "const title = t(`toast.${name}`);",
+        "const title = t('toast.' + name);",
+        "const title = t(getKey());",
+        "const title = t(cond ? 'a' : 'b');",
+      ];
+      for source_text in sources {
+        assert_eq!(parse(source_text), vec![], "{source_text}");
+
+        let config = Config { on_unresolved: OnUnresolved::Placeholder, ..Default::default() };
+        let keys = parse_with_config(source_text, config);
+        assert_eq!(keys, vec![Entry::empty(UNRESOLVED_PLACEHOLDER)], "{source_text}");
+      }
+
+      // language=javascript
+      let source_text = "const title = t(`toast.title`);";
+      let config = Config { on_unresolved: OnUnresolved::Placeholder, ..Default::default() };
+      assert_eq!(parse_with_config(source_text, config), vec![Entry::empty("toast.title")]);
+    }
+
     #[test_log::test]
     fn should_parse_t_with_key_only() {
       // language=javascript
@@ -712,6 +1670,266 @@ mod tests {
       assert_eq!(keys, vec![Entry::empty("toast.title")]);
     }
 
+    #[test_log::test]
+    fn should_not_extract_t_referenced_in_hook_dependencies() {
+      // language=javascript
+      let source_text =
+        "const title = useMemo(() => t('memo.title'), [t]); const cb = useCallback(() => t('cb.title'), [t]);";
+      let keys = parse(source_text);
+
+      assert_eq!(keys.len(), 2);
+      assert_eq!(keys, vec![Entry::empty("memo.title"), Entry::empty("cb.title")]);
+    }
+
+    #[test_log::test]
+    fn should_apply_interpolation_escape_to_default_value() {
+      // language=javascript
+      let source_text = "const title = t('toast.title', 'Hello {{- name}} from {{city}}');";
+
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("toast.title", "Hello {{- name}} from {{city}}")]);
+
+      let config = Config { interpolation_escape: InterpolationEscape::Escaped, ..Default::default() };
+      let keys = parse_with_config(source_text, config);
+      assert_eq!(keys, vec![Entry::new_with_value("toast.title", "Hello {{name}} from {{city}}")]);
+
+      let config = Config { interpolation_escape: InterpolationEscape::Unescaped, ..Default::default() };
+      let keys = parse_with_config(source_text, config);
+      assert_eq!(keys, vec![Entry::new_with_value("toast.title", "Hello {{- name}} from {{- city}}")]);
+    }
+
+    #[test_log::test]
+    fn should_keep_icu_default_value_as_written() {
+      // language=javascript
+      let source_text = "const title = t('items', '{count, plural, one {# item} other {{count} items}}');";
+      let config = Config { interpolation_escape: InterpolationEscape::Unescaped, ..Default::default() };
+      let keys = parse_with_config(source_text, config);
+      assert_eq!(keys, vec![Entry::new_with_value("items", "{count, plural, one {# item} other {{count} items}}")]);
+    }
+
+    #[test_log::test]
+    fn should_keep_the_colon_in_the_key_without_ns_separator() {
+      // language=javascript
+      let source_text = "const title = t('a:b.c', { nsSeparator: false });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::empty("a:b.c")]);
+      assert_eq!(keys[0].i18next_options.as_ref().unwrap().get("nsSeparator"), Some(&Some("false".to_string())));
+
+      // language=javascript
+      let source_text = "const title = t('a:b.c', { nsSeparator: true });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("b.c", "a")]);
+    }
+
+    #[test_log::test]
+    fn should_not_resolve_interpolated_template_literal_keys_and_namespaces() {
+      // language=javascript
+      let source_text = r#"
+        const { t } = useTranslation({ ns: `${ns}` });
+        const title = t(`${key}`!);
+        const other = t('other', { defaultValue: `Hello ${name}` });
+      "#;
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("other", "Hello {{name}}")]);
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_template_literal_default_value() {
+      // language=javascript
+      let source_text = "const title = t('greeting', `Hello ${name}`);";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("greeting", "Hello {{name}}")]);
+
+      // language=javascript
+      let source_text = "const title = t('greeting', `Hello ${user.name}!`, { count: 1 });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("greeting", "Hello {{user.name}}!")]);
+
+      // language=javascript
+      let source_text = "const title = t('greeting', { defaultValue: `Hello ${name}` });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("greeting", "Hello {{name}}")]);
+    }
+
+    #[test_log::test]
+    fn should_prefix_keys_with_use_translation_key_prefix() {
+      // language=javascript
+      let source_text = "const { t } = useTranslation({ keyPrefix: 'section' }); const title = t('title');";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::empty("section.title")]);
+
+      // language=javascript
+      let source_text = "const { t } = useTranslation({ ns: 'ns', keyPrefix: 'section' }); const title = t('title');";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("section.title", "ns")]);
+
+      // language=javascript
+      let source_text = "const { t } = useTranslation('ns', { keyPrefix: 'section' }); const title = t('title');";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("section.title", "ns")]);
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_awaited_default_value() {
+      // language=javascript
+      let source_text = "const DEFAULT = 'Hello'; const title = t('greeting', await DEFAULT);";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("greeting", "Hello")]);
+
+      // language=javascript
+      let source_text = "const DEFAULT = 'Hello'; const title = t('greeting', { defaultValue: await DEFAULT });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("greeting", "Hello")]);
+
+      // language=javascript
+      let source_text = "const title = t('greeting', await getDefault());";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::empty("greeting")]);
+    }
+
+    #[test_log::test]
+    fn should_parse_renamed_t_from_use_translation() {
+      // language=javascript
+      let source_text = "const { t: translate } = useTranslation('ns'); const title = translate('toast.title');";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
+    }
+
+    #[test_log::test]
+    fn should_capture_count_variable() {
+      // language=javascript
+      let source_text =
+        "t('items', { count }); t('files', '{{count}} files', { count: files.length }); t('total', { count: 2 });";
+      let keys = parse(source_text);
+      let count_vars = keys.iter().map(|entry| entry.count_var.as_deref()).collect::<Vec<_>>();
+      assert_eq!(count_vars, vec![Some("count"), Some("files.length"), None]);
+      assert!(keys.iter().all(|entry| entry.has_count));
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_plural_default_values() {
+      // language=javascript
+      let source_text = "const title = t('items', { count: 2, defaultValue: ['one item', '{{count}} items'] });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("items", "one item")]);
+      let options = keys[0].i18next_options.as_ref().unwrap();
+      assert_eq!(options.get("defaultValue[0]"), Some(&Some("one item".to_string())));
+      assert_eq!(options.get("defaultValue[1]"), Some(&Some("{{count}} items".to_string())));
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_context_default_value() {
+      // language=javascript
+      let source_text = "const title = t('x', { context: 'male', defaultValue: 'Them', defaultValue_male: 'Him' });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("x_male", "Him")]);
+
+      // language=javascript
+      let source_text = "const title = t('x', { context: 'female', defaultValue: 'Them', defaultValue_male: 'Him' });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("x_female", "Them")]);
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_enum_member_key() {
+      // language=typescript
+      let source_text = "const enum Keys { Title = 'toast.title', Body = 'toast.body' } const title = t(Keys.Title);";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::empty("toast.title")]);
+
+      // language=typescript
+      let source_text = "export enum Ns { Common = 'common' } const title = t('toast.title', { ns: Ns.Common });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "common")]);
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_shorthand_options() {
+      // language=javascript
+      let source_text = "const ns = 'ns'; const title = t('toast.title', { ns });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
+
+      // language=javascript
+      let source_text = "const namespace = 'ns'; const title = t('toast.title', { namespace });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
+
+      // language=javascript
+      let source_text = "const context = 'male'; const title = t('friend', { context });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::empty("friend_male")]);
+    }
+
+    #[test_log::test]
+    fn should_parse_keys_from_key_functions() {
+      // language=javascript
+      let source_text = "if (i18n.exists('a.b')) { i18n.exists('ns:c.d'); }";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::empty("a.b"), Entry::new_with_ns("c.d", "ns")]);
+      assert_eq!(keys[0].value, None);
+
+      // language=javascript
+      let source_text = "const has = hasKey('a.b') && i18n.exists('c.d');";
+      let keys = parse_with_config(source_text, Config { key_functions: vec!["hasKey".into()], ..Default::default() });
+      assert_eq!(keys, vec![Entry::empty("a.b")]);
+
+      // language=javascript
+      let source_text = "if (fs.exists('./file') || cache.exists('x') || exists('y')) {}";
+      assert_eq!(parse(source_text), vec![]);
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_spread_options() {
+      // language=javascript
+      let source_text = "const opts = { count: 1, ns: 'ns' }; const title = t('k', { ...opts });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("k", "ns")]);
+      assert!(keys[0].has_count);
+
+      // language=javascript
+      let source_text = "const opts = { ns: 'ns' }; const title = t('k', { ...opts, ns: 'other' });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("k", "other")]);
+      assert!(!keys[0].has_count);
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_cyclic_spread_options() {
+      // language=javascript
+      let source_text = "const a = { ns: 'ns', ...b }; const b = { count: 1, ...a }; const title = t('k', { ...a });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("k", "ns")]);
+      assert!(keys[0].has_count);
+
+      // language=javascript
+      let source_text = "const opts = { ...opts, ns: 'ns' }; const title = t('k', { ...opts });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("k", "ns")]);
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_non_null_key() {
+      // language=javascript
+      let source_text = "const key = 'ns:toast.title'; const title = t(key!);";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
+
+      // language=javascript
+      let source_text = "const ns = 'ns'; const title = t('toast.title', { ns: ns! });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_ns("toast.title", "ns")]);
+    }
+
+    #[test_log::test]
+    fn should_parse_t_inside_map_callback() {
+      // language=javascript
+      let source_text = "const labels = items.map((item) => ({ title: t('static.key'), value: item.value }));";
+      let keys = parse(source_text);
+
+      assert_eq!(keys, vec![Entry::empty("static.key")]);
+    }
+
     #[test_log::test]
     fn should_parse_t_with_options() {
       // language=javascript
@@ -832,6 +2050,28 @@ mod tests {
       assert!(el.has_count);
     }
 
+    #[test_log::test]
+    fn should_parse_t_with_count_bigint() {
+      // language=javascript
+      let source_text = "const title = t('toast.title', { count: 1000000000000n });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::empty("toast.title")]);
+      let el = keys.first().unwrap();
+      assert!(el.has_count);
+      let count = el.i18next_options.as_ref().and_then(|o| o.get("count").cloned().flatten());
+      assert_eq!(count, Some("1000000000000".into()));
+    }
+
+    #[test_log::test]
+    fn should_parse_t_with_count_large_numeric() {
+      // language=javascript
+      let source_text = "const title = t('toast.title', { count: 99999999999999999999 });";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::empty("toast.title")]);
+      let el = keys.first().unwrap();
+      assert!(el.has_count);
+    }
+
     #[test_log::test]
     fn should_parse_t_with_count_arg() {
       // language=javascript
@@ -927,6 +2167,27 @@ mod tests {
 
   mod trans_component {
     use super::*;
+    use crate::helper::dot_path_to_hash::{dot_path_to_hash, Conflict};
+
+    /// Collects the values of the entries in a catalog, returning the conflicts found along the way
+    fn find_conflicts(entries: &[Entry]) -> Vec<Conflict> {
+      let config = Config::default();
+      let mut catalog = serde_json::Value::Object(Default::default());
+      entries.iter().filter_map(|entry| dot_path_to_hash(entry, &mut catalog, None, &config).conflict).collect()
+    }
+
+    #[test_log::test]
+    fn should_report_conflicting_children_of_the_same_key() {
+      // language=javascript
+      let source_text = "<><Trans i18nKey='x'>A</Trans><Trans i18nKey='x'>B</Trans><Trans i18nKey='x'>B</Trans></>";
+      let keys = parse(source_text);
+      assert_eq!(find_conflicts(&keys), vec![Conflict::Value("A".into(), "B".into())]);
+
+      // language=javascript
+      let source_text = "<><Trans i18nKey='x'>A</Trans>{t('x', 'B')}</>";
+      let keys = parse(source_text);
+      assert_eq!(find_conflicts(&keys), vec![Conflict::Value("A".into(), "B".into())]);
+    }
 
     #[test_log::test]
     fn should_extract_default_value_from_string_litteral_prop() {
@@ -944,6 +2205,60 @@ mod tests {
       assert_eq!(keys, vec![Entry::new_with_value("first", "test-value")]);
     }
 
+    #[test_log::test]
+    fn should_extract_default_value_from_conditional_prop() {
+      // language=javascript
+      let source_text = "<Trans i18nKey='first' defaults={isAdmin ? 'Admin' : 'User'}>should be ignored</Trans>";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("first", "Admin")]);
+
+      // language=javascript
+      let source_text = "const admin = false; <Trans i18nKey='first' defaults={admin ? 'Admin' : 'User'} />";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("first", "User")]);
+    }
+
+    #[test_log::test]
+    fn should_preserve_spaces_around_tags() {
+      // language=javascript
+      let source_text = "<Trans i18nKey='first'>Hello <strong>world</strong>!</Trans>";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("first", "Hello <1>world</1>!")]);
+
+      // language=javascript
+      let source_text = "<Trans i18nKey='first'>\n  Hello <strong>dear</strong> world\n  <i>again</i>\n</Trans>";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("first", "Hello <1>dear</1> world<3>again</3>")]);
+    }
+
+    #[test_log::test]
+    fn should_split_the_namespace_of_the_i18n_key() {
+      // language=javascript
+      let source_text = "<Trans i18nKey='common:dialog.title'>Title</Trans>";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new("dialog.title", "Title", "common")]);
+
+      // language=javascript
+      let source_text = "<Trans i18nKey='common:dialog.title' ns='other'>Title</Trans>";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new("common:dialog.title", "Title", "other")]);
+    }
+
+    #[test_log::test]
+    fn should_derive_the_key_from_children_without_i18n_key() {
+      // language=javascript
+      let source_text = "<Trans>\n  Hello <strong>world</strong>!\n</Trans>";
+      assert_eq!(parse(source_text), vec![]);
+
+      let keys = parse_with_config(source_text, Config { trans_key_from_children: true, ..Default::default() });
+      assert_eq!(keys, vec![Entry::new_with_value("Hello <1>world</1>!", "Hello <1>world</1>!")]);
+
+      // language=javascript
+      let source_text = "<Trans i18nKey='first'>Hello</Trans>";
+      let keys = parse_with_config(source_text, Config { trans_key_from_children: true, ..Default::default() });
+      assert_eq!(keys, vec![Entry::new_with_value("first", "Hello")]);
+    }
+
     #[test_log::test]
     fn should_extract_key_from_self_closing() {
       // language=javascript
@@ -970,6 +2285,16 @@ mod tests {
       assert_eq!(keys, vec![Entry::new_with_value("beforeafter", "beforeafter")]);
     }
 
+    #[test_log::test]
+    fn should_capture_count_variable() {
+      // language=javascript
+      let source_text = "<Trans i18nKey='items' count={items.length}>{{count}} items</Trans>";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("items", "{{count}} items")]);
+      assert!(keys[0].has_count);
+      assert_eq!(keys[0].count_var.as_deref(), Some("items.length"));
+    }
+
     #[test_log::test]
     fn should_not_add_empty_for_self_closing_tags() {
       // language=javascript
@@ -1011,6 +2336,64 @@ mod tests {
       assert_eq!(first.value, Some("My dogs are named: <1></1>".into()));
     }
 
+    #[test_log::test]
+    fn should_omit_dynamic_list_children() {
+      // language=javascript
+      let source_text = "<Trans i18nKey='dogs'>My dogs are named: <ul i18nIsDynamicList>{['rupert', 'max'].map(dog => (<li>{dog}</li>))}</ul>!</Trans>";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("dogs", "My dogs are named: <1></1>!")]);
+    }
+
+    #[test_log::test]
+    fn should_use_the_first_namespace_of_an_array_ns_prop() {
+      // language=javascript
+      let source_text = "<Trans ns={['common', 'forms']} i18nKey='title'>Title</Trans>";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new("title", "Title", "common")]);
+    }
+
+    #[test_log::test]
+    fn should_extract_conditionally_rendered_trans() {
+      // language=javascript
+      let source_text = "const Title = () => <h1>{show && <Trans i18nKey='x'>y</Trans>}</h1>;";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("x", "y")]);
+
+      // language=javascript
+      let source_text = "const Title = () => show ? <Trans i18nKey='x'>y</Trans> : null;";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("x", "y")]);
+    }
+
+    #[test_log::test]
+    fn should_extract_nested_trans_components() {
+      // language=javascript
+      let source_text =
+        "<Trans i18nKey='outer'>Hello <Trans i18nKey='inner'>world</Trans>, <strong>welcome</strong></Trans>";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![
+        Entry::new_with_value("outer", "Hello <1></1>, <3>welcome</3>"),
+        Entry::new_with_value("inner", "world")
+      ]);
+    }
+
+    #[test_log::test]
+    fn should_apply_interpolation_escape_to_trans() {
+      // language=javascript
+      let source_text = "<Trans i18nKey='greeting'>Hello {{ name }}</Trans>";
+      let config = Config { interpolation_escape: InterpolationEscape::Unescaped, ..Default::default() };
+      let keys = parse_with_config(source_text, config);
+      assert_eq!(keys, vec![Entry::new_with_value("greeting", "Hello {{- name}}")]);
+    }
+
+    #[test_log::test]
+    fn should_ignore_spread_children() {
+      // language=javascript
+      let source_text = "<Trans i18nKey='spread'>Items: {...items}</Trans>";
+      let keys = parse(source_text);
+      assert_eq!(keys, vec![Entry::new_with_value("spread", "Items: ")]);
+    }
+
     #[test_log::test]
     #[should_panic] // todo: fix this test
     fn should_handle_spread_attributes() {
@@ -1193,4 +2576,75 @@ mod tests {
       assert!(le.has_count);
     }
   }
+
+  mod entry_events {
+    use std::sync::{
+      atomic::{AtomicU64, Ordering},
+      Arc,
+      Mutex,
+    };
+
+    use tracing::{field::Field, span, Event, Metadata, Subscriber};
+
+    use super::*;
+
+    /// A subscriber capturing the messages of the events of the `entry` target.
+    #[derive(Default)]
+    struct EntryCapture {
+      messages: Arc<Mutex<Vec<String>>>,
+      next_id: AtomicU64,
+    }
+
+    impl Subscriber for EntryCapture {
+      fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+      }
+
+      fn new_span(&self, _span: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+      }
+
+      fn record(&self, _span: &span::Id, _values: &span::Record<'_>) {
+      }
+
+      fn record_follows_from(&self, _span: &span::Id, _follows: &span::Id) {
+      }
+
+      fn event(&self, event: &Event<'_>) {
+        if event.metadata().target() != "entry" {
+          return;
+        }
+        let mut message = String::new();
+        event.record(&mut |field: &Field, value: &dyn std::fmt::Debug| {
+          if field.name() == "message" {
+            message = format!("{value:?}");
+          }
+        });
+        self.messages.lock().unwrap().push(message);
+      }
+
+      fn enter(&self, _span: &span::Id) {
+      }
+
+      fn exit(&self, _span: &span::Id) {
+      }
+    }
+
+    #[test]
+    fn should_emit_an_event_per_extracted_entry() {
+      let subscriber = EntryCapture::default();
+      let messages = subscriber.messages.clone();
+
+      // language=javascript
+      let source_text = r#"
+        const { t } = useTranslation('common');
+        const title = t('title');
+        const content = <Trans i18nKey="content" ns="home" />;
+      "#;
+      let keys = tracing::subscriber::with_default(subscriber, || parse(source_text));
+
+      assert_eq!(keys.len(), 2);
+      assert_eq!(*messages.lock().unwrap(), vec!["common:title in file.tsx", "home:content in file.tsx"]);
+    }
+  }
 }
@@ -3,6 +3,6 @@ mod i18n_visitor;
 mod node_child;
 pub(crate) mod visit;
 
-pub use entry::Entry;
+pub use entry::{Entry, EntryLocation};
 pub use i18n_visitor::I18NVisitor;
 pub(crate) use i18n_visitor::I18NextOptions;
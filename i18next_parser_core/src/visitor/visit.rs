@@ -1,112 +1,70 @@
 use color_eyre::owo_colors::OwoColorize;
 use log::{debug, trace, warn};
 use oxc_ast::{
-  ast::{Argument, CallExpression, JSXElement, JSXElementName},
+  ast::{Argument, CallExpression, JSXElement, JSXElementName, VariableDeclarator},
   visit::walk,
   Visit,
 };
 use oxc_span::GetSpan;
 
-use crate::{visitor::I18NVisitor, Entry};
-
-#[cfg(debug_assertions)]
-fn print_error_location(span: &oxc_span::Span, file_path: &std::path::PathBuf) {
-  use log::error;
-  let content = std::fs::read_to_string(file_path).unwrap();
-  let (start, remaining) = content.split_at(span.start.try_into().unwrap());
-  let (content, end) = remaining.split_at(span.size().try_into().unwrap());
-  let (previous, previous_content) = start.split_at(start.rfind('\n').unwrap());
-  let (_, last_line) = previous.split_at(previous.rfind('\n').unwrap());
-  let (next_content, next) = end.split_at(end.rfind('\n').unwrap());
-  let (next_line, _) = next.split_at(next.rfind('\n').unwrap());
-  let line = start.chars().fold(0, |i, c| if c == '\n' { i + 1 } else { i });
-
-  error!("Location: ");
-  error!("{line}: {last_line}", last_line = last_line.replace('\n', ""));
-  let line = line + 1;
-  error!(
-    "{line}: {previous_content}{content}{next_content}",
-    previous_content = previous_content.replace('\n', ""),
-    content = content.italic().red().underline(),
-    next_content = next_content.replace('\n', ""),
-  );
-  let line = line + 1;
-  error!("{line}: {next_line}", next_line = next_line.replace('\n', ""));
-}
+use crate::{
+  visitor::{i18n_visitor::TRANS_COMPONENTS, I18NVisitor},
+  Entry,
+};
 
 impl<'a> Visit<'a> for I18NVisitor<'a> {
   fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
     if let Some(name) = expr.callee_name() {
       self.extract_namespace(name, expr);
-      if name == "t" {
+      self.extract_chained_namespace(expr);
+      if self.is_key_function(expr) {
+        if let Some(Argument::StringLiteral(str)) = expr.arguments.first() {
+          trace!("{name} Arg: {:?}", str.bright_black().italic());
+          let (key, namespace) = self.get_namespace(None, &str.value);
+          let namespace = self.infer_namespace(namespace);
+          let location = Some(self.entry_location(expr.span));
+          self.push_entry(Entry { key, namespace, location, ..Default::default() });
+        }
+      } else if self.is_t_function(name) {
         let key = match expr.arguments.first() {
           Some(Argument::StringLiteral(str)) => {
             trace!("t Arg: {:?}", str.bright_black().italic());
             Some(str.value.to_string().clone())
           },
+          Some(Argument::TSNonNullExpression(expression)) => {
+            trace!("t Arg: {:?}", expression.bright_black().italic());
+            self.parse_expression_as_string(&expression.expression).or_else(|| self.unresolved_placeholder())
+          },
+          Some(Argument::Identifier(identifier)) => {
+            trace!("t Arg: {:?}", identifier.bright_black().italic());
+            self.find_identifier_value_as_string(identifier).or_else(|| self.unresolved_placeholder())
+          },
+          Some(Argument::StaticMemberExpression(member)) => {
+            trace!("t Arg: {:?}", member.bright_black().italic());
+            self.parse_member_expression_as_string(member).or_else(|| self.unresolved_placeholder())
+          },
           Some(Argument::TemplateLiteral(template)) => {
             trace!("t Arg: {:?}", template.bright_black().italic());
-            trace!("t quasis: {:?}", template.quasis);
-            trace!("t expressions: {:?}", template.expressions);
-            #[cfg(debug_assertions)]
-            {
-              print_error_location(&template.span, &self.file_path);
-              todo!("Handle template literal")
-            }
-            #[cfg(not(debug_assertions))]
-            {
-              warn!("Template literal are not supported for now");
-              None
+            if template.expressions.is_empty() {
+              Some(Self::template_literal_to_string(template))
+            } else {
+              warn!("Interpolated template literal key cannot be resolved in {}", self.entry_location(template.span));
+              self.unresolved_placeholder()
             }
           },
           Some(Argument::BinaryExpression(bin)) => {
             trace!("t Arg: {:?}", bin.bright_black().italic());
-            #[cfg(debug_assertions)]
-            {
-              print_error_location(&bin.span, &self.file_path);
-              todo!("Handle binary expression")
-            }
-            #[cfg(not(debug_assertions))]
-            {
-              warn!("Binary expression are not supported for now");
-              None
-            }
+            warn!("Binary expression key cannot be resolved in {}", self.entry_location(bin.span));
+            self.unresolved_placeholder()
           },
-
           Some(Argument::CallExpression(call)) => {
             trace!("t Arg: {:?}", call.bright_black().italic());
-            trace!("t callee: {:?}", call.callee);
-            call.common_js_require().inspect(|req| trace!("t require: {}", req.value.to_string()));
-            call.callee_name().inspect(|name| trace!("t callee name: {}", name));
-            trace!("t arguments: {:?}", call.arguments);
-            for (idx, arg) in call.arguments.iter().enumerate() {
-              trace!("t argument: {idx} {:?}", arg);
-            }
-            #[cfg(debug_assertions)]
-            {
-              print_error_location(&call.span, &self.file_path);
-
-              todo!("Handle call expression")
-            }
-            #[cfg(not(debug_assertions))]
-            {
-              warn!("Call expression are not supported for now");
-              None
-            }
+            warn!("Call expression key cannot be resolved in {}", self.entry_location(call.span));
+            self.unresolved_placeholder()
           },
           Some(arg) => {
-            #[cfg(debug_assertions)]
-            {
-              log::error!("Unknown argument type found in [{}]: {arg:?}", self.file_path.display().yellow());
-              print_error_location(&arg.span(), &self.file_path);
-
-              todo!("Handle argument {arg:?} in {}", self.file_path.display().yellow())
-            }
-            #[cfg(not(debug_assertions))]
-            {
-              warn!("Unknown argument type {arg:?}");
-              None
-            }
+            warn!("Unknown argument type {arg:?} in {}", self.entry_location(arg.span()));
+            self.unresolved_placeholder()
           },
           None => {
             warn!("No key provided, skipping entry");
@@ -117,34 +75,53 @@ impl<'a> Visit<'a> for I18NVisitor<'a> {
         if let Some(key) = key {
           trace!("Key: {key}", key = key.italic().cyan());
           let (value, i18next_options) = self.read_t_args((expr.arguments.get(1), expr.arguments.get(2)));
+          let value = value.or_else(|| self.annotated_default_value(expr.span));
 
           let options = i18next_options.as_ref();
           let (key, namespace) = self.get_namespace(options, &key);
+          let namespace = self.infer_namespace(namespace);
+          let key = self.apply_key_prefix(key);
+          let (key, value) = self.apply_context(key, value, options);
           let has_count = match options {
             Some(opt) => opt.get("count").is_some(),
             None => false,
           };
+          let count_var = self.find_count_variable(&expr.arguments);
           for stmt in self.program.body.iter() {
             if stmt.span() == expr.span {
               debug!("Statement: {stmt:?}");
             }
           }
 
-          self.entries.push(Entry { key, value, namespace, has_count, i18next_options });
+          let value = self.escape_default_value(value);
+          let location = Some(self.entry_location(expr.span));
+          let locale = options.and_then(|o| o.get("lng").cloned().flatten());
+          self.push_entry(Entry { key, value, namespace, has_count, count_var, i18next_options, location, locale });
         }
       };
     }
     walk::walk_call_expression(self, expr);
   }
 
+  fn visit_variable_declarator(&mut self, declarator: &VariableDeclarator<'a>) {
+    self.extract_t_alias(declarator);
+    walk::walk_variable_declarator(self, declarator);
+  }
+
   fn visit_jsx_element(&mut self, elem: &JSXElement<'a>) {
-    let component_functions = ["Trans"];
     let name = if let JSXElementName::Identifier(id) = &elem.opening_element.name { Some(&id.name) } else { None };
     #[allow(unused_variables)]
     if let Some(name) = name {
-      if component_functions.contains(&name.as_str()) {
+      if TRANS_COMPONENTS.contains(&name.as_str()) {
         let key = self.get_prop_value(elem, "i18nKey");
         let ns = self.get_prop_value(elem, "ns");
+        let (key, ns) = match (key, ns) {
+          (Some(key), None) => {
+            let (key, ns) = self.split_namespace(&key);
+            (Some(key), ns)
+          },
+          (key, ns) => (key, ns),
+        };
         let default_value = self.get_prop_value(elem, "defaults");
         let count = self.has_prop(elem, "count");
         let options = self.get_prop_value(elem, "i18n");
@@ -155,16 +132,23 @@ impl<'a> Visit<'a> for I18NVisitor<'a> {
           self.elem_to_string(&content)
         };
         trace!("Element as string: {node_as_string:?}");
+        let key = key.or_else(|| {
+          (self.options.trans_key_from_children && !node_as_string.is_empty()).then(|| node_as_string.clone())
+        });
         let default_value = default_value.unwrap_or(node_as_string);
 
         if let Some(key) = key {
-          self.entries.push(Entry {
+          let entry = Entry {
             key,
-            value: if default_value.is_empty() { None } else { Some(default_value) },
-            namespace: ns,
+            value: self.escape_default_value(if default_value.is_empty() { None } else { Some(default_value) }),
+            namespace: self.infer_namespace(ns),
             has_count: count,
+            count_var: self.get_prop_variable_name(elem, "count"),
             i18next_options: options.and_then(|v| serde_json::from_str(&v).ok()),
-          });
+            location: Some(self.entry_location(elem.span)),
+            locale: None,
+          };
+          self.push_entry(entry);
         }
       }
     }
@@ -63,6 +63,7 @@ pub struct PluralOperands {
   pub t: u64,
 }
 
+#[allow(clippy::try_from_instead_of_from_str)]
 impl<'a> TryFrom<&'a str> for PluralOperands {
   type Error = &'static str;
 
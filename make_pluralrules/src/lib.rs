@@ -6,10 +6,15 @@
 //! ```text
 //! cargo run -- -i <./path/to/cldr.json>... -o <./path/to/output.rs>
 //! ```
+//!
+//! With `--split`, the output is a module directory holding a `mod.rs` and one file per language instead
+//! ```text
+//! cargo run -- -i <./path/to/cldr.json>... -o <./path/to/rules> --split
+//! ```
 
 mod parser;
 
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, path::PathBuf};
 
 use color_eyre::eyre::{eyre, OptionExt};
 use proc_macro2::TokenStream;
@@ -17,10 +22,44 @@ use unic_langid::LanguageIdentifier;
 
 use crate::parser::{plural_category::PluralCategory, resource::*};
 
+/// The TokenStreams of the plural rules of each language, by plural rule type.
+type PluralRuleTokens = BTreeMap<String, Vec<(LanguageIdentifier, TokenStream)>>;
+
 /// Takes a string representation of a CLDR JSON file and produces a string representation of the generated Rust code for the plural rules.
 ///
 /// The string representation of the Rust code is written to a specified Rust file and can be used to get the plural category for numerical input.
 pub fn generate_rs(cldr_jsons: &[String]) -> color_eyre::Result<String> {
+  let (tokens, cldr_version) = parse_resources(cldr_jsons)?;
+  let tokens =
+    tokens.into_iter().map(|(pr_type, rules)| (pr_type, rules.into_iter().map(|(_, rule)| rule).collect())).collect();
+  // Call gen_rs to get Rust code. Convert TokenStream to string for file out.
+  Ok(parser::gen_rs::gen_fn(tokens, &cldr_version).to_string())
+}
+
+/// Takes string representations of CLDR JSON files and produces the generated Rust code split per language.
+///
+/// The files are meant to be written in a module directory: a `mod.rs` holding the plural rule tables and one file per
+/// language holding its plural rules, so that each of them is compiled separately.
+///
+/// # Returns
+///
+/// The path of each file, relative to the module directory, along with its content.
+pub fn generate_rs_split(cldr_jsons: &[String]) -> color_eyre::Result<Vec<(PathBuf, String)>> {
+  let (tokens, cldr_version) = parse_resources(cldr_jsons)?;
+  Ok(
+    parser::gen_rs::gen_split(tokens, &cldr_version)
+      .into_iter()
+      .map(|(name, content)| (PathBuf::from(name), content.to_string()))
+      .collect(),
+  )
+}
+
+/// Parses the CLDR JSON files into the TokenStreams of the plural rules of each language, by plural rule type.
+///
+/// # Returns
+///
+/// The plural rules by type along with the CLDR version shared by every file.
+fn parse_resources(cldr_jsons: &[String]) -> color_eyre::Result<(PluralRuleTokens, String)> {
   let mut cldr_version = None;
   let mut tokens = BTreeMap::new();
 
@@ -57,14 +96,15 @@ pub fn generate_rs(cldr_jsons: &[String]) -> color_eyre::Result<String> {
     return Err(eyre!("None of the input files provided core data!"));
   }
 
-  let cldr_version = &cldr_version.ok_or_eyre("No CLDR version found in input files!")?;
-  // Call gen_rs to get Rust code. Convert TokenStream to string for file out.
-  Ok(parser::gen_rs::gen_fn(tokens, cldr_version).to_string())
+  let cldr_version = cldr_version.ok_or_eyre("No CLDR version found in input files!")?;
+  Ok((tokens, cldr_version))
 }
 
-fn gen_type_rs(rules: BTreeMap<String, BTreeMap<String, String>>) -> color_eyre::Result<Vec<TokenStream>> {
+fn gen_type_rs(
+  rules: BTreeMap<String, BTreeMap<String, String>>,
+) -> color_eyre::Result<Vec<(LanguageIdentifier, TokenStream)>> {
   // rule_tokens is a vector of TokenStreams that represent the CLDR plural rules as Rust expressions.
-  let mut rule_tokens = Vec::<TokenStream>::new();
+  let mut rule_tokens = Vec::<(LanguageIdentifier, TokenStream)>::new();
 
   let mut rules: Vec<(LanguageIdentifier, BTreeMap<String, String>)> = rules
     .into_iter()
@@ -101,7 +141,8 @@ fn gen_type_rs(rules: BTreeMap<String, BTreeMap<String, String>>) -> color_eyre:
       }
     }
     // convert language rules to TokenStream and add them to all the rules
-    rule_tokens.push(parser::gen_rs::gen_mid(&lang, &this_lang_rules)?);
+    let tokens = parser::gen_rs::gen_mid(&lang, &this_lang_rules)?;
+    rule_tokens.push((lang, tokens));
   }
 
   Ok(rule_tokens)
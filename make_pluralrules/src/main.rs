@@ -1,7 +1,7 @@
 use std::{fs, io::Write, path::PathBuf, process::Command};
 
 use clap::Parser;
-use make_pluralrules::generate_rs;
+use make_pluralrules::{generate_rs, generate_rs_split};
 
 /// Initialize the panic handler.
 pub fn initialize_panic_handler() -> color_eyre::Result<()> {
@@ -52,9 +52,12 @@ struct Cli {
   /// Input CLDR JSON plural rules files
   #[arg(short, long)]
   input: Vec<PathBuf>,
-  /// Output RS file
+  /// Output RS file, or module directory with `--split`
   #[arg(short, long)]
   output: PathBuf,
+  /// Split the output per language into a module directory with a `mod.rs`
+  #[arg(short, long, default_value = "false")]
+  split: bool,
   /// Do not run `rustfmt` on the output file
   #[arg(short, long, default_value = "false")]
   ugly: bool,
@@ -67,15 +70,25 @@ fn main() -> color_eyre::Result<()> {
 
   let input_jsons =
     input_paths.iter().map(|path| fs::read_to_string(path).expect("file not found")).collect::<Vec<_>>();
-  let complete_rs_code = generate_rs(&input_jsons)?;
-
   let output_path = &cli.output;
-  let mut file = fs::File::create(output_path)?;
-  file.write_all(complete_rs_code.as_bytes())?;
+  let files = if cli.split {
+    fs::create_dir_all(output_path)?;
+    generate_rs_split(&input_jsons)?.into_iter().map(|(path, content)| (output_path.join(path), content)).collect()
+  } else {
+    vec![(output_path.clone(), generate_rs(&input_jsons)?)]
+  };
+
+  for (path, content) in &files {
+    let mut file = fs::File::create(path)?;
+    file.write_all(content.as_bytes())?;
+  }
 
   if !cli.ugly {
     println!("Running cargo fmt on {output_path:?}");
-    Command::new("rustfmt").args([output_path]).output().expect("Failed to format the output using `rustfmt`");
+    Command::new("rustfmt")
+      .args(files.iter().map(|(path, _)| path))
+      .output()
+      .expect("Failed to format the output using `rustfmt`");
   }
 
   Ok(())
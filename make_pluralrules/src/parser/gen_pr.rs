@@ -2,6 +2,8 @@
 //!
 //! The resulting token stream is used to generate a Rust code representation of CLDR plural rules. That Rust code is then used to determine the corresponding plural rule for a number.
 
+use std::str::FromStr;
+
 /// This code utilizes the cldr_pluralrules_parser AST
 use cldr_pluralrules_parser::ast::*;
 /// proc_macro2 provides the TokenStream type
@@ -13,6 +15,13 @@ fn convert_literal(num: usize) -> Literal {
   Literal::u64_unsuffixed(num as u64)
 }
 
+/// Convert an integer Literal to a float Literal, so that it can be compared to the `f64` operand `n`
+///
+/// Appending `.0` to the tokens of the integer would output `1 . 0`, a field access that only compiles once formatted.
+fn convert_float_literal(num: &Literal) -> Literal {
+  Literal::from_str(&format!("{num}.0")).expect("An integer followed by `.0` is a float literal")
+}
+
 /// Convert a usize pair into a tuple of literals
 fn convert_range(low: usize, up: usize) -> (Literal, Literal) {
   let u = convert_literal(up);
@@ -80,7 +89,8 @@ fn create_relation(rel: Relation) -> TokenStream {
     // Variants handled here
     let (rfront, rback, whole_symbol) = if left.operand == Operand::N {
       if !mod_check {
-        (quote!(#rfront.0), quote!(#rback.0), quote!(po.#l))
+        let (rfront, rback) = (convert_float_literal(rfront), convert_float_literal(rback));
+        (quote!(#rfront), quote!(#rback), quote!(po.#l))
       } else {
         (quote!(#rfront), quote!(#rback), quote!(po.i % #m))
       }
@@ -97,7 +107,8 @@ fn create_relation(rel: Relation) -> TokenStream {
       // Variants handled here
       let (symbol, rval) = if left.operand == Operand::N {
         if !mod_check {
-          (quote!(po.#l), quote!(#r.0))
+          let r = convert_float_literal(&r);
+          (quote!(po.#l), quote!(#r))
         } else {
           (quote!(po.i % #m), quote!(#r))
         }
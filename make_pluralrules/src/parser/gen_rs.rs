@@ -2,14 +2,14 @@
 use std::{collections::BTreeMap, str};
 
 use color_eyre::{eyre::eyre, owo_colors::OwoColorize};
-use proc_macro2::{Literal, TokenStream};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::quote;
 use unic_langid::LanguageIdentifier;
 
 use super::plural_category::PluralCategory;
 
-/// Generates the complete TokenStream for the generated Rust code. This wraps the head and tail of the .rs file around the generated CLDR expressions.
-pub fn gen_fn(streams: BTreeMap<String, Vec<TokenStream>>, vr: &str) -> TokenStream {
+/// Generates the head of the generated Rust code: the lint exceptions, the imports, the CLDR version and the macros.
+fn gen_head(vr: &str) -> TokenStream {
   let ignore_noncritical_errors = quote! {
       #![allow(unused_variables, unused_parens, clippy::float_cmp, clippy::unreadable_literal, clippy::nonminimal_bool)]
   };
@@ -39,7 +39,12 @@ pub fn gen_fn(streams: BTreeMap<String, Vec<TokenStream>>, vr: &str) -> TokenStr
   let num: isize = vr.parse().unwrap();
   let ver = Literal::u64_unsuffixed(num as u64);
   let version = quote! { pub static CLDR_VERSION: usize = #ver; };
-  let head = quote! { #ignore_noncritical_errors #use_statements #plural_function #version #langid_macro };
+  quote! { #ignore_noncritical_errors #use_statements #plural_function #version #langid_macro }
+}
+
+/// Generates the complete TokenStream for the generated Rust code. This wraps the head and tail of the .rs file around the generated CLDR expressions.
+pub fn gen_fn(streams: BTreeMap<String, Vec<TokenStream>>, vr: &str) -> TokenStream {
+  let head = gen_head(vr);
   let mut tokens = Vec::<TokenStream>::new();
   for (pr_type, stream) in streams {
     tokens.push(create_pr_type(&pr_type, stream));
@@ -48,6 +53,53 @@ pub fn gen_fn(streams: BTreeMap<String, Vec<TokenStream>>, vr: &str) -> TokenStr
   quote! { #head #prs }
 }
 
+/// Generates the TokenStreams of a module directory, one file per language and a `mod.rs` gathering them.
+///
+/// Each language module holds a `CARDINAL` and/or `ORDINAL` constant, referenced by the tables of the `mod.rs`.
+///
+/// # Returns
+///
+/// The name of each file, starting with `mod.rs`, along with its TokenStream.
+pub fn gen_split(
+  streams: BTreeMap<String, Vec<(LanguageIdentifier, TokenStream)>>,
+  vr: &str,
+) -> Vec<(String, TokenStream)> {
+  let head = gen_head(vr);
+  let mut languages = BTreeMap::<String, Vec<TokenStream>>::new();
+  let mut tables = Vec::<TokenStream>::new();
+
+  for (pr_type, stream) in streams {
+    let const_name = match pr_type.as_str() {
+      "cardinal" => quote! { CARDINAL },
+      "ordinal" => quote! { ORDINAL },
+      _ => panic!("Unknown plural rule type"),
+    };
+
+    let mut entries = Vec::<TokenStream>::new();
+    for (lang, func) in stream {
+      let module_name = gen_module_name(&lang);
+      let module = Ident::new(&module_name, Span::call_site());
+      entries.push(quote! { #module::#const_name });
+      languages
+        .entry(module_name)
+        .or_default()
+        .push(quote! { pub const #const_name: (LanguageIdentifier, PluralRule, &[PluralCategory]) = #func; });
+    }
+    tables.push(create_pr_type(&pr_type, entries));
+  }
+
+  let modules = languages.keys().map(|name| Ident::new(name, Span::call_site())).collect::<Vec<_>>();
+  let mut files = vec![("mod.rs".to_string(), quote! { #head #(mod #modules;)* #(#tables)* })];
+  files
+    .extend(languages.into_iter().map(|(name, consts)| (format!("{name}.rs"), quote! { use super::*; #(#consts)* })));
+  files
+}
+
+/// Generates the name of the module holding the plural rules of a language, prefixed so that it is never a keyword.
+fn gen_module_name(lang: &LanguageIdentifier) -> String {
+  format!("lang_{}", lang.to_string().to_lowercase().replace('-', "_"))
+}
+
 // Function wraps all match statements for plural rules in a match for ordinal and cardinal rules
 fn create_pr_type(pr_type: &str, streams: Vec<TokenStream>) -> TokenStream {
   let mut tokens = Vec::<TokenStream>::new();
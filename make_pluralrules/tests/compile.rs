@@ -0,0 +1,85 @@
+//! Compiles the generated plural rules against a stub of the resolver API of `intl_pluralrules`.
+//!
+//! The fixture is compared to the generated code by the other tests, so a generated code that does not satisfy the
+//! resolver API fails to build this test.
+use pretty_assertions::assert_eq;
+use unic_langid::LanguageIdentifier;
+
+// the fixture is compared to the generated code as is, so it is neither formatted nor linted
+#[rustfmt::skip]
+#[allow(unused_braces, clippy::double_parens)]
+#[path = "fixtures/cldr_pluralrules_45.rs"]
+mod rules;
+
+/// Stub of the operands of `intl_pluralrules`, holding every operand read by the generated rules.
+mod operands {
+  #[derive(Debug, Default, PartialEq)]
+  pub struct PluralOperands {
+    pub c: usize,
+    pub e: usize,
+    pub n: f64,
+    pub i: u64,
+    pub v: usize,
+    pub w: usize,
+    pub f: u64,
+    pub t: u64,
+  }
+}
+
+use operands::PluralOperands;
+
+/// Stub of the plural categories of `intl_pluralrules`.
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Eq, PartialEq)]
+pub enum PluralCategory {
+  ZERO,
+  ONE,
+  TWO,
+  FEW,
+  MANY,
+  OTHER,
+}
+
+/// The rules of each language, sorted by language identifier for a binary search, along with their categories.
+type PluralRulesTable =
+  &'static [(LanguageIdentifier, fn(&PluralOperands) -> PluralCategory, &'static [PluralCategory])];
+
+/// The resolver API of `intl_pluralrules` that the generated plural rules must satisfy.
+trait PluralRulesSource {
+  /// The CLDR version the rules were generated from.
+  const CLDR_VERSION: usize;
+  /// The cardinal plural rules.
+  const CARDINAL: PluralRulesTable;
+  /// The ordinal plural rules.
+  const ORDINAL: PluralRulesTable;
+}
+
+/// The plural rules generated from the fixture.
+struct Generated;
+
+impl PluralRulesSource for Generated {
+  const CARDINAL: PluralRulesTable = rules::PRS_CARDINAL;
+  const CLDR_VERSION: usize = rules::CLDR_VERSION;
+  const ORDINAL: PluralRulesTable = rules::PRS_ORDINAL;
+}
+
+fn select(table: PluralRulesTable, lang: &str, operands: PluralOperands) -> &'static PluralCategory {
+  let langid: LanguageIdentifier = lang.parse().expect("Parsing of the language succeeded");
+  let idx = table.binary_search_by_key(&&langid, |(langid, _, _)| langid).expect("Language is present");
+  let (_, rule, categories) = &table[idx];
+  let category = rule(&operands);
+  categories.iter().find(|available| **available == category).expect("Category is available")
+}
+
+#[test]
+fn generated_rules_satisfy_the_resolver_api() {
+  assert_eq!(45, Generated::CLDR_VERSION);
+
+  let one = || PluralOperands { n: 1.0, i: 1, ..Default::default() };
+  let two = || PluralOperands { n: 2.0, i: 2, ..Default::default() };
+
+  assert_eq!(&PluralCategory::ONE, select(Generated::CARDINAL, "en", one()));
+  assert_eq!(&PluralCategory::OTHER, select(Generated::CARDINAL, "en", two()));
+  assert_eq!(&PluralCategory::TWO, select(Generated::ORDINAL, "en", two()));
+  assert_eq!(&PluralCategory::ONE, select(Generated::CARDINAL, "pt-PT", one()));
+}
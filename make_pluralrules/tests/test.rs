@@ -1,7 +1,7 @@
 use std::{fs::File, io, io::Read};
 
 use color_eyre::Result;
-use make_pluralrules::generate_rs;
+use make_pluralrules::{generate_rs, generate_rs_split};
 use pretty_assertions::assert_eq;
 
 fn read_file(path: &str) -> Result<String, io::Error> {
@@ -48,6 +48,29 @@ fn within_test() -> Result<()> {
   Ok(())
 }
 
+#[test]
+fn split_test() -> Result<()> {
+  let cardinal_json = read_file("./tests/fixtures/cldr_pluralrules_cardinals_33.json")?;
+  let ordinal_json = read_file("./tests/fixtures/cldr_pluralrules_ordinals_33.json")?;
+
+  let files = generate_rs_split(&[cardinal_json, ordinal_json])?;
+  let (mod_path, mod_rs) = &files[0];
+
+  assert_eq!(mod_path.to_str(), Some("mod.rs"));
+  assert!(files.len() > 2);
+  assert!(files.iter().any(|(path, _)| path.to_str() == Some("lang_en.rs")));
+  assert!(files.iter().any(|(path, _)| path.to_str() == Some("lang_pt_pt.rs")));
+  assert!(mod_rs.contains("mod lang_en ;"));
+  assert!(mod_rs.contains("lang_en :: CARDINAL"));
+  assert!(mod_rs.contains("lang_en :: ORDINAL"));
+
+  let (_, en_rs) = files.iter().find(|(path, _)| path.to_str() == Some("lang_en.rs")).unwrap();
+  assert!(en_rs.contains("pub const CARDINAL"));
+  assert!(en_rs.contains("pub const ORDINAL"));
+
+  Ok(())
+}
+
 #[test]
 #[should_panic]
 fn bad_type_test() {